@@ -8,4 +8,54 @@ pub enum StakeError {
     IncorrectCollection,
     #[msg("Collection not verified")]
     CollectionNotVerified,
+    #[msg("Max stake reached")]
+    MaxStakeReached,
+    #[msg("Freeze period not passed")]
+    FreezePeriodNotPassed,
+    #[msg("Invalid stake account")]
+    InvalidStakeAccount,
+    #[msg("Invalid weight proof")]
+    InvalidWeightProof,
+    #[msg("Invalid lock tier")]
+    InvalidLockTier,
+    #[msg("Lock period not over")]
+    LockNotExpired,
+    #[msg("Penalty cannot exceed 10000 basis points")]
+    InvalidPenalty,
+    #[msg("NFT is not programmable")]
+    NotProgrammable,
+    #[msg("Invalid compressed NFT metadata")]
+    InvalidMetadata,
+    #[msg("Invalid emission schedule")]
+    InvalidEmissionSchedule,
+    #[msg("Reward vault is empty")]
+    RewardsExhausted,
+    #[msg("Reward vault account missing")]
+    MissingRewardVault,
+    #[msg("Rewards are not paid from a vault")]
+    NotVaultRewards,
+    #[msg("Token staking already enabled")]
+    TokenStakingEnabled,
+    #[msg("Withdraw amount exceeds staked amount")]
+    InsufficientStake,
+    #[msg("Max stake exceeds the staked mints a user account can hold")]
+    InvalidMaxStake,
+    #[msg("Staking is paused")]
+    Paused,
+    #[msg("Staking is not paused")]
+    NotPaused,
+    #[msg("Season must end after it starts")]
+    InvalidSeason,
+    #[msg("Season already finalized")]
+    SeasonFinalized,
+    #[msg("Season not over")]
+    SeasonNotOver,
+    #[msg("Invalid user season account")]
+    InvalidUserSeason,
+    #[msg("Reward vault already initialized")]
+    RewardVaultInitialized,
+    #[msg("Only the collection's update authority can create its config")]
+    NotCollectionAuthority,
+    #[msg("Arithmetic overflow")]
+    Overflow,
 }
\ No newline at end of file
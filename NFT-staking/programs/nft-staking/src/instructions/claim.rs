@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, token::{mint_to, Mint, MintTo, Token, TokenAccount}};
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{mint_to, transfer_checked, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked}};
 
-use crate::state::{StakeConfig, UserAccount};
+use crate::{errors::StakeError, instructions::season_entry::*, state::{RewardMode, StakeAccount, StakeConfig, TokenStakeAccount, UserAccount}};
 
 #[derive(Accounts)]
 pub struct Claim<'info> {
@@ -9,19 +9,20 @@ pub struct Claim<'info> {
     pub user: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"user".as_ref(), user.key().as_ref()],
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
         bump = user_account.bump,
     )]
     pub user_account: Account<'info, UserAccount>,
     #[account(
         mut,
-        seeds = [b"rewards".as_ref(), config.key().as_ref()],
-        bump = config.rewards_bump
+        address = config.rewards_mint,
     )]
-    pub rewards_mint: Account<'info, Mint>,
+    pub rewards_mint: InterfaceAccount<'info, Mint>,
     #[account(
-        seeds = [b"config".as_ref()],
-        bump = config.bump
+        mut,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ StakeError::Paused,
     )]
     pub config: Account<'info, StakeConfig>,
     #[account(
@@ -29,20 +30,48 @@ pub struct Claim<'info> {
         payer = user,
         associated_token::mint = rewards_mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub rewards_ata: InterfaceAccount<'info, TokenAccount>,
+    // Accrued alongside the NFT stakes when the user also stakes fungible tokens
+    #[account(
+        mut,
+        seeds = [b"token_stake".as_ref(), config.key().as_ref(), user.key().as_ref()],
+        bump = token_stake.bump,
+    )]
+    pub token_stake: Option<Account<'info, TokenStakeAccount>>,
+    #[account(constraint = season_entry.belongs_to(config.key(), user.key()) @ StakeError::InvalidUserSeason)]
+    pub season_entry: SeasonEntry<'info>,
+    // Only used when rewards are paid from a pre-funded vault
+    #[account(
+        mut,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
     )]
-    pub rewards_ata: Account<'info, TokenAccount>,
+    pub reward_vault: Option<InterfaceAccount<'info, TokenAccount>>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 impl<'info> Claim<'info> {
-    pub fn claim(&mut self) -> Result<()> {
+    pub fn claim(&mut self, stake_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+
+        self.accrue(stake_accounts)?;
 
+        match self.config.reward_mode {
+            RewardMode::Mint => self.mint_rewards(),
+            RewardMode::Vault => self.transfer_rewards(),
+        }
+    }
+
+    fn mint_rewards(&mut self) -> Result<()> {
         let cpi_program = self.token_program.to_account_info();
 
         let seeds = &[
             b"config".as_ref(),
+            self.config.collection.as_ref(),
             &[self.config.bump]
         ];     
         let signer_seeds = &[&seeds[..]];
@@ -55,10 +84,105 @@ impl<'info> Claim<'info> {
 
         let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
 
-        mint_to(cpi_context, self.user_account.points as u64 * 10_u64.pow(self.rewards_mint.decimals as u32))?;
+        let amount = self.user_account.points
+            .checked_mul(10_u64.pow(self.rewards_mint.decimals as u32))
+            .ok_or(StakeError::Overflow)?;
+
+        mint_to(cpi_context, amount)?;
 
         self.user_account.points = 0;
         
         Ok(())
     }
+
+    // Pays out as many whole points as the vault still covers. Points it can't cover stay on
+    // the user account so they can be claimed once the vault is topped up.
+    fn transfer_rewards(&mut self) -> Result<()> {
+        if self.user_account.points == 0 {
+            return Ok(());
+        }
+
+        let reward_vault = self.reward_vault.as_ref().ok_or(StakeError::MissingRewardVault)?;
+
+        let unit = 10_u64.pow(self.rewards_mint.decimals as u32);
+        let points = self.user_account.points.min(reward_vault.amount / unit);
+        require!(points > 0, StakeError::RewardsExhausted);
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let seeds = &[
+            b"config".as_ref(),
+            self.config.collection.as_ref(),
+            &[self.config.bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: reward_vault.to_account_info(),
+            mint: self.rewards_mint.to_account_info(),
+            to: self.rewards_ata.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(cpi_context, points * unit, self.rewards_mint.decimals)?;
+
+        self.user_account.points -= points;
+
+        Ok(())
+    }
+
+    // Accrues points for every unlocked NFT still staked, passed in as writable `StakeAccount`s, and
+    // the user's token stake if there is one, counting them toward the user's season as they go.
+    pub fn accrue(&mut self, stake_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        self.config.update_pool(now);
+
+        let mut season = self.season_entry.entry();
+
+        for info in stake_accounts {
+            let mut stake_account = Account::<StakeAccount>::try_from(info)?;
+
+            require_keys_eq!(stake_account.owner, self.user.key(), StakeError::InvalidStakeAccount);
+
+            let expected = Pubkey::create_program_address(
+                &[
+                    b"stake",
+                    stake_account.mint.as_ref(),
+                    self.config.key().as_ref(),
+                    &[stake_account.bump],
+                ],
+                &crate::ID,
+            ).map_err(|_| StakeError::InvalidStakeAccount)?;
+            require_keys_eq!(expected, info.key(), StakeError::InvalidStakeAccount);
+
+            // Locked stakes keep their points until they're unstaked, so an early unlock
+            // penalty can't be dodged by claiming first.
+            if now < stake_account.lock_until {
+                continue;
+            }
+
+            let since = stake_account.last_accrued;
+            let points = stake_account.accrue_points(&self.config, now);
+            if let Some((season, user_season)) = season.as_mut() {
+                season.record(user_season, points, since, now);
+            }
+            self.user_account.add_points(points);
+
+            stake_account.exit(&crate::ID)?;
+        }
+
+        if let Some(token_stake) = self.token_stake.as_mut() {
+            let since = token_stake.last_accrued;
+            let points = token_stake.accrue_points(&self.config, now);
+            if let Some((season, user_season)) = season.as_mut() {
+                season.record(user_season, points, since, now);
+            }
+            self.user_account.add_points(points);
+        }
+
+        Ok(())
+    }
 }
\ No newline at end of file
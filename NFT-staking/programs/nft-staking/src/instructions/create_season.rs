@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StakeError, state::{Season, StakeConfig}};
+
+#[derive(Accounts)]
+#[instruction(id: u32)]
+pub struct CreateSeason<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = Season::INIT_SPACE,
+        seeds = [b"season".as_ref(), config.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub season: Account<'info, Season>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateSeason<'info> {
+    pub fn create_season(&mut self, id: u32, start_time: i64, end_time: i64, bumps: &CreateSeasonBumps) -> Result<()> {
+        require!(start_time < end_time, StakeError::InvalidSeason);
+
+        self.season.set_inner(Season {
+            config: self.config.key(),
+            id,
+            start_time,
+            end_time,
+            finalized: false,
+            leaderboard: Vec::new(),
+            bump: bumps.season,
+        });
+
+        Ok(())
+    }
+}
\ No newline at end of file
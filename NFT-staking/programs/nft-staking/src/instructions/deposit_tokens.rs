@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{errors::StakeError, instructions::season_entry::*, state::{StakeConfig, TokenStakeAccount, UserAccount}};
+
+#[derive(Accounts)]
+pub struct DepositTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ StakeError::Paused,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(address = config.stake_mint)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"token_vault".as_ref(), config.key().as_ref()],
+        bump = config.token_vault_bump,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = TokenStakeAccount::INIT_SPACE,
+        seeds = [b"token_stake".as_ref(), config.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub token_stake: Account<'info, TokenStakeAccount>,
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(constraint = season_entry.belongs_to(config.key(), user.key()) @ StakeError::InvalidUserSeason)]
+    pub season_entry: SeasonEntry<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> DepositTokens<'info> {
+    pub fn deposit_tokens(&mut self, amount: u64, bumps: &DepositTokensBumps) -> Result<()> {
+
+        if self.token_stake.owner == Pubkey::default() {
+            self.token_stake.owner = self.user.key();
+            self.token_stake.bump = bumps.token_stake;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+
+        self.config.update_pool(now);
+        let since = self.token_stake.last_accrued;
+        let points = self.token_stake.accrue_points(&self.config, now);
+        if let Some((season, user_season)) = self.season_entry.entry() {
+            season.record(user_season, points, since, now);
+        }
+        self.user_account.add_points(points);
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.user_ata.to_account_info(),
+            mint: self.stake_mint.to_account_info(),
+            to: self.token_vault.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        // Credit what the vault actually received, which is less than `amount` for mints that
+        // charge a transfer fee.
+        let before = self.token_vault.amount;
+        transfer_checked(cpi_ctx, amount, self.stake_mint.decimals)?;
+        self.token_vault.reload()?;
+        let received = self.token_vault.amount - before;
+
+        let staked = self.token_stake.amount + received;
+        self.token_stake.set_amount(&mut self.config, staked, self.stake_mint.decimals);
+
+        Ok(())
+    }
+}
\ No newline at end of file
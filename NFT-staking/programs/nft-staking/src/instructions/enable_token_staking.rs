@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{errors::StakeError, state::StakeConfig};
+
+#[derive(Accounts)]
+pub struct EnableTokenStaking<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+        constraint = !config.token_staking_enabled() @ StakeError::TokenStakingEnabled,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(mint::token_program = token_program)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    // Kept apart from the reward vault so staked principal is never paid out as rewards,
+    // even when the staked token is also the reward token.
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"token_vault".as_ref(), config.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = config,
+        token::token_program = token_program,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> EnableTokenStaking<'info> {
+    pub fn enable_token_staking(&mut self, token_weight: u64, bumps: &EnableTokenStakingBumps) -> Result<()> {
+        self.config.stake_mint = self.stake_mint.key();
+        self.config.token_weight = token_weight;
+        self.config.token_vault_bump = bumps.token_vault;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StakeError, state::{Season, StakeConfig}};
+
+#[derive(Accounts)]
+pub struct FinalizeSeason<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        mut,
+        has_one = config,
+        constraint = !season.finalized @ StakeError::SeasonFinalized,
+        seeds = [b"season".as_ref(), config.key().as_ref(), season.id.to_le_bytes().as_ref()],
+        bump = season.bump,
+    )]
+    pub season: Account<'info, Season>,
+}
+
+impl<'info> FinalizeSeason<'info> {
+    // Freezes the leaderboard as the season's winners.
+    pub fn finalize_season(&mut self) -> Result<()> {
+        require!(Clock::get()?.unix_timestamp >= self.season.end_time, StakeError::SeasonNotOver);
+
+        self.season.finalized = true;
+
+        Ok(())
+    }
+}
\ No newline at end of file
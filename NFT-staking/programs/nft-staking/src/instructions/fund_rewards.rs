@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{errors::StakeError, state::{RewardMode, StakeConfig}};
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    pub funder: Signer<'info>,
+    #[account(
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+        constraint = config.reward_mode == RewardMode::Vault @ StakeError::NotVaultRewards,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(address = config.rewards_mint)]
+    pub rewards_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = rewards_mint,
+        token::authority = funder,
+        token::token_program = token_program,
+    )]
+    pub funder_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FundRewards<'info> {
+    pub fn fund_rewards(&mut self, amount: u64) -> Result<()> {
+        let cpi_program = self.token_program.to_account_info();
+
+        let cpi_accounts = TransferChecked {
+            from: self.funder_ata.to_account_info(),
+            mint: self.rewards_mint.to_account_info(),
+            to: self.reward_vault.to_account_info(),
+            authority: self.funder.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.rewards_mint.decimals)
+    }
+}
\ No newline at end of file
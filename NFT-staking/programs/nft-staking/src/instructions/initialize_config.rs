@@ -1,16 +1,29 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token};
+use anchor_spl::{metadata::{Metadata, MetadataAccount}, token::{Mint, Token}};
 
-use crate::state::StakeConfig;
+use crate::{errors::StakeError, state::{EmissionSchedule, RewardMode, StakeConfig, MAX_STAKED_MINTS}};
 
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
+    pub collection: Account<'info, Mint>,
+    // Configs are keyed by collection, so only its update authority may create one
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            collection.key().as_ref()
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+        constraint = metadata.update_authority == admin.key() @ StakeError::NotCollectionAuthority,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
     #[account(
         init, 
         payer = admin,
-        seeds = [b"config".as_ref()],
+        seeds = [b"config".as_ref(), collection.key().as_ref()],
         bump,
         space = StakeConfig::INIT_SPACE,
     )]
@@ -26,18 +39,39 @@ pub struct InitializeConfig<'info> {
     pub rewards_mint: Account<'info, Mint>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
+    pub metadata_program: Program<'info, Metadata>,
 }
 
 impl<'info> InitializeConfig<'info> {
-    pub fn initialize_config(&mut self, points_per_stake: u8, max_stake: u8, freeze_period: u32, bumps: &InitializeConfigBumps) -> Result<()> {
+    pub fn initialize_config(&mut self, max_stake: u8, freeze_period: u32, emission: EmissionSchedule, bumps: &InitializeConfigBumps) -> Result<()> {
+        require!(max_stake as usize <= MAX_STAKED_MINTS, StakeError::InvalidMaxStake);
+        require!(emission.epoch_duration > 0, StakeError::InvalidEmissionSchedule);
+
         self.config.set_inner(StakeConfig {
-            points_per_stake,
+            admin: self.admin.key(),
+            collection: self.collection.key(),
             max_stake,
             freeze_period,
+            weights_root: [0u8; 32],
+            lock_tiers: StakeConfig::default_lock_tiers(),
+            early_unlock_penalty_bps: 0,
+            emission,
+            total_weight: 0,
+            acc_points_per_weight: 0,
+            emitted: 0,
+            reward_mode: RewardMode::Mint,
+            rewards_mint: self.rewards_mint.key(),
+            stake_mint: Pubkey::default(),
+            token_weight: 0,
+            token_vault_bump: 0,
+            paused: false,
             rewards_bump: bumps.rewards_mint,
             bump: bumps.config,
         });
 
+        // A start time in the past doesn't backdate emissions to nobody
+        self.config.update_pool(Clock::get()?.unix_timestamp);
+
         Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token_interface::{Mint, TokenAccount, TokenInterface}};
+
+use crate::{errors::StakeError, state::{RewardMode, StakeConfig}};
+
+// Switches the config to paying rewards from a vault of an existing SPL or Token-2022 mint,
+// owned by the config PDA. Points are worth one whole token of the new mint from then on.
+// This can only happen once: swapping the mint later would strand the old vault's tokens.
+#[derive(Accounts)]
+pub struct InitializeRewardVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+        constraint = config.reward_mode == RewardMode::Mint @ StakeError::RewardVaultInitialized,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(mint::token_program = token_program)]
+    pub rewards_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = config,
+        associated_token::token_program = token_program,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> InitializeRewardVault<'info> {
+    pub fn initialize_reward_vault(&mut self) -> Result<()> {
+        self.config.reward_mode = RewardMode::Vault;
+        self.config.rewards_mint = self.rewards_mint.key();
+
+        Ok(())
+    }
+}
\ No newline at end of file
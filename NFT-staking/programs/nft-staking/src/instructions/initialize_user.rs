@@ -1,15 +1,20 @@
 use anchor_lang::prelude::*;
 
-use crate::state::UserAccount;
+use crate::state::{StakeConfig, UserAccount};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
+    #[account(
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
     #[account(
         init,
         payer = user,
-        seeds = [b"user".as_ref(), user.key().as_ref()],
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
         bump,
         space = UserAccount::INIT_SPACE,
     )]
@@ -23,6 +28,9 @@ impl<'info> Initialize<'info> {
         self.user_account.set_inner(UserAccount { 
             points: 0, 
             amount_staked: 0, 
+            staked_mints: Vec::new(),
+            total_points_earned: 0,
+            total_staked_seconds: 0,
             bump: bumps.user_account 
         });
 
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StakeError, state::{Season, StakeConfig, UserAccount, UserSeason}};
+
+#[derive(Accounts)]
+pub struct JoinSeason<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        has_one = config,
+        constraint = !season.finalized @ StakeError::SeasonFinalized,
+        seeds = [b"season".as_ref(), config.key().as_ref(), season.id.to_le_bytes().as_ref()],
+        bump = season.bump,
+    )]
+    pub season: Account<'info, Season>,
+    #[account(
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(
+        init,
+        payer = user,
+        space = UserSeason::INIT_SPACE,
+        seeds = [b"user_season".as_ref(), season.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_season: Account<'info, UserSeason>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> JoinSeason<'info> {
+    pub fn join_season(&mut self, bumps: &JoinSeasonBumps) -> Result<()> {
+        self.user_season.set_inner(UserSeason {
+            season: self.season.key(),
+            user: self.user.key(),
+            points: 0,
+            joined_at: Clock::get()?.unix_timestamp,
+            bump: bumps.user_season,
+        });
+
+        Ok(())
+    }
+}
\ No newline at end of file
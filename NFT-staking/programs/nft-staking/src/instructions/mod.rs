@@ -1,11 +1,39 @@
 pub mod initialize_config;
+pub mod update_config;
 pub mod initialize_user;
 pub mod stake;
 pub mod unstake;
+pub mod stake_pnft;
+pub mod unstake_pnft;
+pub mod stake_cnft;
+pub mod unstake_cnft;
 pub mod claim;
+pub mod initialize_reward_vault;
+pub mod fund_rewards;
+pub mod enable_token_staking;
+pub mod deposit_tokens;
+pub mod withdraw_tokens;
+pub mod create_season;
+pub mod join_season;
+pub mod finalize_season;
+pub mod season_entry;
 
 pub use initialize_config::*;
+pub use update_config::*;
 pub use initialize_user::*;
 pub use stake::*;
 pub use unstake::*;
-pub use claim::*;
\ No newline at end of file
+pub use stake_pnft::*;
+pub use unstake_pnft::*;
+pub use stake_cnft::*;
+pub use unstake_cnft::*;
+pub use claim::*;
+pub use initialize_reward_vault::*;
+pub use fund_rewards::*;
+pub use enable_token_staking::*;
+pub use deposit_tokens::*;
+pub use withdraw_tokens::*;
+pub use create_season::*;
+pub use join_season::*;
+pub use finalize_season::*;
+pub use season_entry::*;
\ No newline at end of file
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Season, UserSeason};
+
+// Optional season accounts, passed together to count the points an instruction accrues toward a
+// running season. The PDAs tie the user's entry to the season, and the instruction checks both
+// belong to its config and user with `belongs_to`.
+#[derive(Accounts)]
+pub struct SeasonEntry<'info> {
+    #[account(
+        mut,
+        seeds = [b"season".as_ref(), season.config.as_ref(), season.id.to_le_bytes().as_ref()],
+        bump = season.bump,
+    )]
+    pub season: Option<Account<'info, Season>>,
+    #[account(
+        mut,
+        has_one = season,
+        seeds = [b"user_season".as_ref(), user_season.season.as_ref(), user_season.user.as_ref()],
+        bump = user_season.bump,
+    )]
+    pub user_season: Option<Account<'info, UserSeason>>,
+}
+
+impl<'info> SeasonEntry<'info> {
+    pub fn belongs_to(&self, config: Pubkey, user: Pubkey) -> bool {
+        self.season.as_ref().is_none_or(|season| season.config == config)
+            && self.user_season.as_ref().is_none_or(|user_season| user_season.user == user)
+    }
+
+    // The season and the user's entry in it, when both were passed.
+    pub fn entry(&mut self) -> Option<(&mut Season, &mut UserSeason)> {
+        match (self.season.as_mut(), self.user_season.as_mut()) {
+            (Some(season), Some(user_season)) => Some((&mut **season, &mut **user_season)),
+            _ => None,
+        }
+    }
+}
\ No newline at end of file
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{metadata::{mpl_token_metadata::instructions::{FreezeDelegatedAccountCpi, FreezeDelegatedAccountCpiAccounts}, MasterEditionAccount, Metadata, MetadataAccount}, token::{approve, Approve, Mint, Token, TokenAccount}};
 
-use crate::state::{StakeAccount, StakeConfig, UserAccount};
+use crate::{errors::StakeError, state::{StakeAccount, StakeConfig, UserAccount}};
 
 #[derive(Accounts)]
 pub struct Stake<'info> {
@@ -23,8 +23,8 @@ pub struct Stake<'info> {
         ],
         seeds::program = metadata_program.key(),
         bump,
-        constraint = metadata.collection.as_ref().unwrap().key.as_ref() == collection.key().as_ref(),
-        constraint = metadata.collection.as_ref().unwrap().verified == true,
+        constraint = metadata.collection.as_ref().is_some_and(|c| c.key == collection.key()) @ StakeError::IncorrectCollection,
+        constraint = metadata.collection.as_ref().is_some_and(|c| c.verified) @ StakeError::CollectionNotVerified,
     )]
     pub metadata: Account<'info, MetadataAccount>,
     #[account(
@@ -38,6 +38,12 @@ pub struct Stake<'info> {
         bump,
     )]
     pub edition: Account<'info, MasterEditionAccount>,
+    #[account(
+        mut,
+        seeds = [b"config".as_ref(), collection.key().as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ StakeError::Paused,
+    )]
     pub config: Account<'info, StakeConfig>,
     #[account(
         init,
@@ -49,7 +55,7 @@ pub struct Stake<'info> {
     pub stake_account: Account<'info, StakeAccount>,
     #[account(
         mut,
-        seeds = [b"user".as_ref(), user.key().as_ref()],
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
         bump = user_account.bump,
     )]
     pub user_account: Account<'info, UserAccount>,
@@ -59,14 +65,19 @@ pub struct Stake<'info> {
 }
 
 impl<'info> Stake<'info> {
-    pub fn stake(&mut self, bumps: &StakeBumps) -> Result<()> {
+    pub fn stake(&mut self, multiplier: u16, proof: Vec<[u8; 32]>, lock_tier: u8, bumps: &StakeBumps) -> Result<()> {
 
-        self.stake_account.set_inner(StakeAccount {
-            owner: self.user.key(),
-            mint: self.mint.key(),
-            last_update: Clock::get()?.unix_timestamp,
-            bump: bumps.stake_account,
-        });
+        self.stake_account.set_inner(StakeAccount::new(
+            &mut self.config,
+            self.user.key(),
+            self.mint.key(),
+            multiplier,
+            &proof,
+            lock_tier,
+            bumps.stake_account,
+        )?);
+
+        self.user_account.record_stake(&self.config, self.mint.key())?;
 
         let cpi_program = self.token_program.to_account_info();
 
@@ -106,9 +117,6 @@ impl<'info> Stake<'info> {
             },
         ).invoke_signed(signer_seeds)?;
 
-        //require!(self.user_account.amount_staked < self.config.max_stake, "Max stake reached");
-        self.user_account.amount_staked += 1;
-
         Ok(())
     }
 }
\ No newline at end of file
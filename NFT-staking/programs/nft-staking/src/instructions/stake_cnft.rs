@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use mpl_bubblegum::{hash::{hash_creators, hash_metadata}, instructions::{TransferCpi, TransferCpiAccounts, TransferInstructionArgs}, programs::{SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID}, types::MetadataArgs, utils::get_asset_id};
+
+use crate::{errors::StakeError, state::{StakeAccount, StakeConfig, UserAccount}};
+
+// Compressed NFTs have no token account to freeze, so the leaf is transferred into the custody
+// of the stake account PDA. Bubblegum verifies the concurrent Merkle proof (passed as remaining
+// accounts) against the tree as part of the transfer, which proves the user owns the leaf.
+#[derive(Accounts)]
+#[instruction(root: [u8; 32], nonce: u64)]
+pub struct StakeCnft<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: current leaf delegate, part of the leaf hash verified by Bubblegum
+    pub leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: tree authority PDA of the Merkle tree, validated by Bubblegum
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        seeds::program = bubblegum_program.key(),
+        bump,
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: concurrent Merkle tree, validated by the account compression program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    pub collection: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"config".as_ref(), collection.key().as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ StakeError::Paused,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        init,
+        payer = user,
+        space = StakeAccount::INIT_SPACE,
+        seeds = [b"stake".as_ref(), get_asset_id(&merkle_tree.key(), nonce).as_ref(), config.key().as_ref()],
+        bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    /// CHECK: Bubblegum program
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: SPL Noop program
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: SPL Account Compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StakeCnft<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn stake_cnft(&mut self, root: [u8; 32], nonce: u64, index: u32, metadata: Vec<u8>, multiplier: u16, proof: Vec<[u8; 32]>, lock_tier: u8, bumps: &StakeCnftBumps, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+
+        // The leaf commits to the hash of the metadata, so once the transfer below verifies the
+        // leaf, the collection read from this metadata is the cNFT's real collection.
+        let metadata_args = MetadataArgs::try_from_slice(&metadata).map_err(|_| StakeError::InvalidMetadata)?;
+
+        let collection = metadata_args.collection.as_ref().ok_or(StakeError::IncorrectCollection)?;
+        require_keys_eq!(collection.key, self.config.collection, StakeError::IncorrectCollection);
+        require!(collection.verified, StakeError::CollectionNotVerified);
+
+        let data_hash = hash_metadata(&metadata_args).map_err(|_| StakeError::InvalidMetadata)?;
+        let creator_hash = hash_creators(&metadata_args.creators);
+
+        let asset_id = get_asset_id(&self.merkle_tree.key(), nonce);
+
+        self.stake_account.set_inner(StakeAccount::new(
+            &mut self.config,
+            self.user.key(),
+            asset_id,
+            multiplier,
+            &proof,
+            lock_tier,
+            bumps.stake_account,
+        )?);
+
+        self.user_account.record_stake(&self.config, asset_id)?;
+
+        let proof_accounts: Vec<(&AccountInfo<'info>, bool, bool)> = remaining_accounts
+            .iter()
+            .map(|account| (account, false, false))
+            .collect();
+
+        TransferCpi::new(
+            &self.bubblegum_program.to_account_info(),
+            TransferCpiAccounts {
+                tree_config: &self.tree_config.to_account_info(),
+                leaf_owner: (&self.user.to_account_info(), true),
+                leaf_delegate: (&self.leaf_delegate.to_account_info(), false),
+                new_leaf_owner: &self.stake_account.to_account_info(),
+                merkle_tree: &self.merkle_tree.to_account_info(),
+                log_wrapper: &self.log_wrapper.to_account_info(),
+                compression_program: &self.compression_program.to_account_info(),
+                system_program: &self.system_program.to_account_info(),
+            },
+            TransferInstructionArgs {
+                root,
+                data_hash,
+                creator_hash,
+                nonce,
+                index,
+            },
+        ).invoke_with_remaining_accounts(&proof_accounts)?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
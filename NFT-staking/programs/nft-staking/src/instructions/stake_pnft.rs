@@ -0,0 +1,179 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::{metadata::{mpl_token_metadata::{instructions::{DelegateStakingV1Cpi, DelegateStakingV1CpiAccounts, DelegateStakingV1InstructionArgs, LockV1Cpi, LockV1CpiAccounts, LockV1InstructionArgs}, types::TokenStandard}, MasterEditionAccount, Metadata, MetadataAccount}, token::{Mint, Token, TokenAccount}};
+
+use crate::{errors::StakeError, state::{StakeAccount, StakeConfig, UserAccount}};
+
+// Programmable NFTs can't be frozen with `FreezeDelegatedAccount`, so the stake account is made
+// the staking delegate through Token Metadata and locks the token instead. Token Metadata checks
+// the transfer against the collection's rule set using the token record and authorization rules.
+#[derive(Accounts)]
+pub struct StakePnft<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    pub collection: Account<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub mint_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+        constraint = metadata.collection.as_ref().is_some_and(|c| c.key == collection.key()) @ StakeError::IncorrectCollection,
+        constraint = metadata.collection.as_ref().is_some_and(|c| c.verified) @ StakeError::CollectionNotVerified,
+        constraint = metadata.token_standard == Some(TokenStandard::ProgrammableNonFungible) @ StakeError::NotProgrammable,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+            b"edition"
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub edition: Account<'info, MasterEditionAccount>,
+    /// CHECK: token record of the user's token account, owned and validated by Token Metadata
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+            b"token_record",
+            mint_ata.key().as_ref()
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub token_record: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"config".as_ref(), collection.key().as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ StakeError::Paused,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        init,
+        payer = user,
+        space = StakeAccount::INIT_SPACE,
+        seeds = [b"stake".as_ref(), mint.key().as_ref(), config.key().as_ref()],
+        bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    /// CHECK: rule set from the metadata's programmable config, validated by Token Metadata
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+    /// CHECK: Token Auth Rules program, validated by Token Metadata
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+impl<'info> StakePnft<'info> {
+    pub fn stake_pnft(&mut self, multiplier: u16, proof: Vec<[u8; 32]>, lock_tier: u8, bumps: &StakePnftBumps) -> Result<()> {
+
+        self.stake_account.set_inner(StakeAccount::new(
+            &mut self.config,
+            self.user.key(),
+            self.mint.key(),
+            multiplier,
+            &proof,
+            lock_tier,
+            bumps.stake_account,
+        )?);
+
+        self.user_account.record_stake(&self.config, self.mint.key())?;
+
+        let user = &self.user.to_account_info();
+        let delegate = &self.stake_account.to_account_info();
+        let token = &self.mint_ata.to_account_info();
+        let mint = &self.mint.to_account_info();
+        let metadata = &self.metadata.to_account_info();
+        let edition = &self.edition.to_account_info();
+        let token_record = &self.token_record.to_account_info();
+        let authorization_rules = self.authorization_rules.as_ref().map(|a| a.to_account_info());
+        let authorization_rules_program = self.authorization_rules_program.as_ref().map(|a| a.to_account_info());
+        let sysvar_instructions = &self.sysvar_instructions.to_account_info();
+        let system_program = &self.system_program.to_account_info();
+        let token_program = &self.token_program.to_account_info();
+        let metadata_program = &self.metadata_program.to_account_info();
+
+        DelegateStakingV1Cpi::new(
+            metadata_program,
+            DelegateStakingV1CpiAccounts {
+                delegate_record: None,
+                delegate,
+                metadata,
+                master_edition: Some(edition),
+                token_record: Some(token_record),
+                mint,
+                token,
+                authority: user,
+                payer: user,
+                system_program,
+                sysvar_instructions,
+                spl_token_program: Some(token_program),
+                authorization_rules_program: authorization_rules_program.as_ref(),
+                authorization_rules: authorization_rules.as_ref(),
+            },
+            DelegateStakingV1InstructionArgs {
+                amount: 1,
+                authorization_data: None,
+            },
+        ).invoke()?;
+
+        let seeds = &[
+            b"stake",
+            self.mint.to_account_info().key.as_ref(),
+            self.config.to_account_info().key.as_ref(),
+            &[self.stake_account.bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        LockV1Cpi::new(
+            metadata_program,
+            LockV1CpiAccounts {
+                authority: delegate,
+                token_owner: Some(user),
+                token,
+                mint,
+                metadata,
+                edition: Some(edition),
+                token_record: Some(token_record),
+                payer: user,
+                system_program,
+                sysvar_instructions,
+                spl_token_program: Some(token_program),
+                authorization_rules_program: authorization_rules_program.as_ref(),
+                authorization_rules: authorization_rules.as_ref(),
+            },
+            LockV1InstructionArgs {
+                authorization_data: None,
+            },
+        ).invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{metadata::{mpl_token_metadata::instructions::{ThawDelegatedAccountCpi, ThawDelegatedAccountCpiAccounts}, MasterEditionAccount, Metadata, MetadataAccount}, token::{revoke, Mint, Revoke, Token, TokenAccount}};
 
-use crate::state::{StakeAccount, StakeConfig, UserAccount};
+use crate::{errors::StakeError, instructions::season_entry::*, state::{StakeAccount, StakeConfig, UserAccount}};
 
 #[derive(Accounts)]
 pub struct Unstake<'info> {
@@ -35,6 +35,11 @@ pub struct Unstake<'info> {
         bump,
     )]
     pub edition: Account<'info, MasterEditionAccount>,
+    #[account(
+        mut,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+    )]
     pub config: Account<'info, StakeConfig>,
     #[account(
         mut,
@@ -45,10 +50,12 @@ pub struct Unstake<'info> {
     pub stake_account: Account<'info, StakeAccount>,
     #[account(
         mut,
-        seeds = [b"user".as_ref(), user.key().as_ref()],
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
         bump = user_account.bump,
     )]
     pub user_account: Account<'info, UserAccount>,
+    #[account(constraint = season_entry.belongs_to(config.key(), user.key()) @ StakeError::InvalidUserSeason)]
+    pub season_entry: SeasonEntry<'info>,
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub metadata_program: Program<'info, Metadata>,
@@ -57,12 +64,21 @@ pub struct Unstake<'info> {
 impl<'info> Unstake<'info> {
     pub fn unstake(&mut self) -> Result<()> {
 
-        let time_elapsed = ((Clock::get()?.unix_timestamp - self.stake_account.last_update) / 86400) as u32;
+        let season = self.season_entry.entry();
+        self.user_account.record_unstake(&mut self.config, &mut self.stake_account, season)?;
+
+        self.release()
+    }
 
-        //require!(time_elapsed >= self.config.freeze_period, "Cannot unstake yet");
+    pub fn emergency_unstake(&mut self) -> Result<()> {
 
-        self.user_account.points += time_elapsed as u32 * self.config.points_per_stake as u32;
+        self.user_account.record_emergency_unstake(&mut self.config, &self.stake_account)?;
 
+        self.release()
+    }
+
+    // Thaws the NFT and revokes the stake account's delegation.
+    fn release(&mut self) -> Result<()> {
         let seeds = &[
             b"stake",
             self.mint.to_account_info().key.as_ref(),
@@ -98,10 +114,6 @@ impl<'info> Unstake<'info> {
 
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-        revoke(cpi_ctx)?;
-
-        self.user_account.amount_staked -= 1;
-
-        Ok(())
+        revoke(cpi_ctx)
     }
 }
\ No newline at end of file
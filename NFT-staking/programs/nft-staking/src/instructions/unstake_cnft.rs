@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use mpl_bubblegum::{instructions::{TransferCpi, TransferCpiAccounts, TransferInstructionArgs}, programs::{SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID}, utils::get_asset_id};
+
+use crate::{errors::StakeError, instructions::season_entry::*, state::{StakeAccount, StakeConfig, UserAccount}};
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32], data_hash: [u8; 32], creator_hash: [u8; 32], nonce: u64)]
+pub struct UnstakeCnft<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: tree authority PDA of the Merkle tree, validated by Bubblegum
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        seeds::program = bubblegum_program.key(),
+        bump,
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: concurrent Merkle tree, validated by the account compression program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        mut,
+        close = user,
+        constraint = stake_account.owner == user.key() @ StakeError::InvalidStakeAccount,
+        seeds = [b"stake".as_ref(), get_asset_id(&merkle_tree.key(), nonce).as_ref(), config.key().as_ref()],
+        bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(constraint = season_entry.belongs_to(config.key(), user.key()) @ StakeError::InvalidUserSeason)]
+    pub season_entry: SeasonEntry<'info>,
+    /// CHECK: Bubblegum program
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: SPL Noop program
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: SPL Account Compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UnstakeCnft<'info> {
+    pub fn unstake_cnft(&mut self, root: [u8; 32], data_hash: [u8; 32], creator_hash: [u8; 32], nonce: u64, index: u32, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+
+        let season = self.season_entry.entry();
+        self.user_account.record_unstake(&mut self.config, &mut self.stake_account, season)?;
+
+        self.release(root, data_hash, creator_hash, nonce, index, remaining_accounts)
+    }
+
+    pub fn emergency_unstake_cnft(&mut self, root: [u8; 32], data_hash: [u8; 32], creator_hash: [u8; 32], nonce: u64, index: u32, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+
+        self.user_account.record_emergency_unstake(&mut self.config, &self.stake_account)?;
+
+        self.release(root, data_hash, creator_hash, nonce, index, remaining_accounts)
+    }
+
+    // Transfers the leaf from the stake account's custody back to the user.
+    fn release(&mut self, root: [u8; 32], data_hash: [u8; 32], creator_hash: [u8; 32], nonce: u64, index: u32, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let seeds = &[
+            b"stake",
+            self.stake_account.mint.as_ref(),
+            self.config.to_account_info().key.as_ref(),
+            &[self.stake_account.bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let proof_accounts: Vec<(&AccountInfo<'info>, bool, bool)> = remaining_accounts
+            .iter()
+            .map(|account| (account, false, false))
+            .collect();
+
+        // The stake account owns the leaf and is also its delegate after the custody transfer
+        let stake_account = &self.stake_account.to_account_info();
+
+        TransferCpi::new(
+            &self.bubblegum_program.to_account_info(),
+            TransferCpiAccounts {
+                tree_config: &self.tree_config.to_account_info(),
+                leaf_owner: (stake_account, true),
+                leaf_delegate: (stake_account, false),
+                new_leaf_owner: &self.user.to_account_info(),
+                merkle_tree: &self.merkle_tree.to_account_info(),
+                log_wrapper: &self.log_wrapper.to_account_info(),
+                compression_program: &self.compression_program.to_account_info(),
+                system_program: &self.system_program.to_account_info(),
+            },
+            TransferInstructionArgs {
+                root,
+                data_hash,
+                creator_hash,
+                nonce,
+                index,
+            },
+        ).invoke_signed_with_remaining_accounts(signer_seeds, &proof_accounts)?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StakeError, state::{EmissionSchedule, LockTier, StakeConfig, BASE_MULTIPLIER, MAX_LOCK_TIERS, MAX_STAKED_MINTS}};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_config(&mut self, max_stake: u8, freeze_period: u32) -> Result<()> {
+        require!(max_stake as usize <= MAX_STAKED_MINTS, StakeError::InvalidMaxStake);
+
+        self.config.max_stake = max_stake;
+        self.config.freeze_period = freeze_period;
+
+        Ok(())
+    }
+
+    pub fn set_lock_tiers(&mut self, lock_tiers: [LockTier; MAX_LOCK_TIERS], early_unlock_penalty_bps: u16) -> Result<()> {
+        require!(early_unlock_penalty_bps <= BASE_MULTIPLIER, StakeError::InvalidPenalty);
+
+        self.config.lock_tiers = lock_tiers;
+        self.config.early_unlock_penalty_bps = early_unlock_penalty_bps;
+
+        Ok(())
+    }
+
+    pub fn set_weights_root(&mut self, weights_root: [u8; 32]) -> Result<()> {
+        self.config.weights_root = weights_root;
+
+        Ok(())
+    }
+
+    // Distributes everything the old schedule emitted up to now before swapping it out. Like
+    // the old one, the new schedule counts its total from its own `start_time` and only emits
+    // once that total passes what's already been emitted; nothing is backdated.
+    pub fn set_emission(&mut self, emission: EmissionSchedule) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        self.config.update_pool(now);
+
+        require!(emission.epoch_duration > 0, StakeError::InvalidEmissionSchedule);
+        require!(emission.max_supply >= self.config.emitted, StakeError::InvalidEmissionSchedule);
+
+        self.config.emission = emission;
+        self.config.emitted = self.config.emitted.max(emission.emitted_at(now));
+
+        Ok(())
+    }
+
+    // Distributes everything emitted up to now before accrual stops.
+    pub fn pause(&mut self) -> Result<()> {
+        self.config.update_pool(Clock::get()?.unix_timestamp);
+        self.config.paused = true;
+
+        Ok(())
+    }
+
+    // Drops the points scheduled while paused, so accrual picks up from now.
+    pub fn unpause(&mut self) -> Result<()> {
+        self.config.update_pool(Clock::get()?.unix_timestamp);
+        self.config.paused = false;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{errors::StakeError, instructions::season_entry::*, state::{StakeConfig, TokenStakeAccount, UserAccount}};
+
+#[derive(Accounts)]
+pub struct WithdrawTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(address = config.stake_mint)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"token_vault".as_ref(), config.key().as_ref()],
+        bump = config.token_vault_bump,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"token_stake".as_ref(), config.key().as_ref(), user.key().as_ref()],
+        bump = token_stake.bump,
+    )]
+    pub token_stake: Account<'info, TokenStakeAccount>,
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(constraint = season_entry.belongs_to(config.key(), user.key()) @ StakeError::InvalidUserSeason)]
+    pub season_entry: SeasonEntry<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawTokens<'info> {
+    // Stays open while the config is paused, as the token counterpart of an emergency unstake.
+    // The pool doesn't accrue while paused, so a paused withdrawal only returns tokens and the
+    // points accrued before the pause.
+    pub fn withdraw_tokens(&mut self, amount: u64) -> Result<()> {
+
+        require!(amount <= self.token_stake.amount, StakeError::InsufficientStake);
+
+        let now = Clock::get()?.unix_timestamp;
+
+        self.config.update_pool(now);
+        let since = self.token_stake.last_accrued;
+        let points = self.token_stake.accrue_points(&self.config, now);
+        if let Some((season, user_season)) = self.season_entry.entry() {
+            season.record(user_season, points, since, now);
+        }
+        self.user_account.add_points(points);
+
+        let staked = self.token_stake.amount - amount;
+        self.token_stake.set_amount(&mut self.config, staked, self.stake_mint.decimals);
+
+        let cpi_program = self.token_program.to_account_info();
+
+        let seeds = &[
+            b"config".as_ref(),
+            self.config.collection.as_ref(),
+            &[self.config.bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: self.token_vault.to_account_info(),
+            mint: self.stake_mint.to_account_info(),
+            to: self.user_ata.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+        transfer_checked(cpi_ctx, amount, self.stake_mint.decimals)
+    }
+}
\ No newline at end of file
@@ -7,28 +7,115 @@ mod instructions;
 mod errors;
 
 pub use instructions::*;
+pub use errors::StakeError;
+pub use state::{EmissionDecay, EmissionSchedule, LockTier, RewardMode, MAX_LOCK_TIERS, MAX_STAKED_MINTS};
 
 #[program]
 pub mod nft_staking {
     use super::*;
 
-    pub fn initialize_config(ctx: Context<InitializeConfig>, points_per_stake: u8, max_stake: u8, freeze_period: u32) -> Result<()> {
-        ctx.accounts.initialize_config(points_per_stake, max_stake, freeze_period, &ctx.bumps)
+    pub fn initialize_config(ctx: Context<InitializeConfig>, max_stake: u8, freeze_period: u32, emission: EmissionSchedule) -> Result<()> {
+        ctx.accounts.initialize_config(max_stake, freeze_period, emission, &ctx.bumps)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, max_stake: u8, freeze_period: u32) -> Result<()> {
+        ctx.accounts.update_config(max_stake, freeze_period)
+    }
+
+    pub fn set_weights_root(ctx: Context<UpdateConfig>, weights_root: [u8; 32]) -> Result<()> {
+        ctx.accounts.set_weights_root(weights_root)
+    }
+
+    pub fn set_lock_tiers(ctx: Context<UpdateConfig>, lock_tiers: [LockTier; MAX_LOCK_TIERS], early_unlock_penalty_bps: u16) -> Result<()> {
+        ctx.accounts.set_lock_tiers(lock_tiers, early_unlock_penalty_bps)
+    }
+
+    pub fn set_emission(ctx: Context<UpdateConfig>, emission: EmissionSchedule) -> Result<()> {
+        ctx.accounts.set_emission(emission)
+    }
+
+    pub fn pause(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.pause()
+    }
+
+    pub fn unpause(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.unpause()
     }
 
     pub fn initialize_user(ctx: Context<Initialize>) -> Result<()> {
         ctx.accounts.initialize_user(&ctx.bumps)
     }
 
-    pub fn stake(ctx: Context<Stake>) -> Result<()> {
-        ctx.accounts.stake(&ctx.bumps)
+    pub fn stake(ctx: Context<Stake>, multiplier: u16, proof: Vec<[u8; 32]>, lock_tier: u8) -> Result<()> {
+        ctx.accounts.stake(multiplier, proof, lock_tier, &ctx.bumps)
     }
 
     pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
         ctx.accounts.unstake()
     }
 
-    pub fn claim(ctx: Context<Claim>) -> Result<()> {
-        ctx.accounts.claim()
+    pub fn emergency_unstake(ctx: Context<Unstake>) -> Result<()> {
+        ctx.accounts.emergency_unstake()
+    }
+
+    pub fn stake_pnft(ctx: Context<StakePnft>, multiplier: u16, proof: Vec<[u8; 32]>, lock_tier: u8) -> Result<()> {
+        ctx.accounts.stake_pnft(multiplier, proof, lock_tier, &ctx.bumps)
+    }
+
+    pub fn unstake_pnft(ctx: Context<UnstakePnft>) -> Result<()> {
+        ctx.accounts.unstake_pnft()
+    }
+
+    pub fn emergency_unstake_pnft(ctx: Context<UnstakePnft>) -> Result<()> {
+        ctx.accounts.emergency_unstake_pnft()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn stake_cnft<'info>(ctx: Context<'_, '_, '_, 'info, StakeCnft<'info>>, root: [u8; 32], nonce: u64, index: u32, metadata: Vec<u8>, multiplier: u16, proof: Vec<[u8; 32]>, lock_tier: u8) -> Result<()> {
+        ctx.accounts.stake_cnft(root, nonce, index, metadata, multiplier, proof, lock_tier, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn unstake_cnft<'info>(ctx: Context<'_, '_, '_, 'info, UnstakeCnft<'info>>, root: [u8; 32], data_hash: [u8; 32], creator_hash: [u8; 32], nonce: u64, index: u32) -> Result<()> {
+        ctx.accounts.unstake_cnft(root, data_hash, creator_hash, nonce, index, ctx.remaining_accounts)
+    }
+
+    pub fn emergency_unstake_cnft<'info>(ctx: Context<'_, '_, '_, 'info, UnstakeCnft<'info>>, root: [u8; 32], data_hash: [u8; 32], creator_hash: [u8; 32], nonce: u64, index: u32) -> Result<()> {
+        ctx.accounts.emergency_unstake_cnft(root, data_hash, creator_hash, nonce, index, ctx.remaining_accounts)
+    }
+
+    pub fn claim<'info>(ctx: Context<'_, '_, 'info, 'info, Claim<'info>>) -> Result<()> {
+        ctx.accounts.claim(ctx.remaining_accounts)
+    }
+
+    pub fn initialize_reward_vault(ctx: Context<InitializeRewardVault>) -> Result<()> {
+        ctx.accounts.initialize_reward_vault()
+    }
+
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.fund_rewards(amount)
+    }
+
+    pub fn enable_token_staking(ctx: Context<EnableTokenStaking>, token_weight: u64) -> Result<()> {
+        ctx.accounts.enable_token_staking(token_weight, &ctx.bumps)
+    }
+
+    pub fn deposit_tokens(ctx: Context<DepositTokens>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit_tokens(amount, &ctx.bumps)
+    }
+
+    pub fn withdraw_tokens(ctx: Context<WithdrawTokens>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_tokens(amount)
+    }
+
+    pub fn create_season(ctx: Context<CreateSeason>, id: u32, start_time: i64, end_time: i64) -> Result<()> {
+        ctx.accounts.create_season(id, start_time, end_time, &ctx.bumps)
+    }
+
+    pub fn join_season(ctx: Context<JoinSeason>) -> Result<()> {
+        ctx.accounts.join_season(&ctx.bumps)
+    }
+
+    pub fn finalize_season(ctx: Context<FinalizeSeason>) -> Result<()> {
+        ctx.accounts.finalize_season()
     }
 }
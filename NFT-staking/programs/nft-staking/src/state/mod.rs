@@ -1,7 +1,11 @@
 pub mod stake_config;
 pub mod stake_account;
 pub mod user_account;
+pub mod token_stake_account;
+pub mod season;
 
 pub use stake_config::*;
 pub use stake_account::*;
-pub use user_account::*;
\ No newline at end of file
+pub use user_account::*;
+pub use token_stake_account::*;
+pub use season::*;
\ No newline at end of file
@@ -0,0 +1,153 @@
+use std::cmp::Reverse;
+
+use anchor_lang::prelude::*;
+
+pub const LEADERBOARD_SIZE: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LeaderboardEntry {
+    pub user: Pubkey,
+    pub points: u64,
+}
+
+impl LeaderboardEntry {
+    pub const SIZE: usize = 32 + 8;
+}
+
+// A competition window on a config. The leaderboard keeps the top `LEADERBOARD_SIZE` users by
+// season points, highest first, and is frozen as the winners once the season is finalized.
+#[account]
+pub struct Season {
+    pub config: Pubkey,
+    pub id: u32,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub finalized: bool,
+    pub leaderboard: Vec<LeaderboardEntry>,
+    pub bump: u8,
+}
+
+impl Space for Season {
+    const INIT_SPACE: usize = 8 + 32 + 4 + 8 + 8 + 1 + 4 + LeaderboardEntry::SIZE * LEADERBOARD_SIZE + 1;
+}
+
+impl Season {
+    // Counts the share of `points` earned while both the season and the user's entry were
+    // running, and moves the user up the leaderboard. The points are taken to have accrued evenly
+    // from `since` to `now`, the span since the stake they came from last accrued.
+    pub fn record(&mut self, user_season: &mut UserSeason, points: u64, since: i64, now: i64) {
+        let from = since.max(self.start_time).max(user_season.joined_at);
+        let to = now.min(self.end_time);
+
+        if self.finalized || points == 0 || to <= from {
+            return;
+        }
+
+        user_season.points += (points as u128 * (to - from) as u128 / (now - since) as u128) as u64;
+        self.rank(user_season.user, user_season.points);
+    }
+
+    fn rank(&mut self, user: Pubkey, points: u64) {
+        if let Some(entry) = self.leaderboard.iter_mut().find(|entry| entry.user == user) {
+            entry.points = points;
+        } else if self.leaderboard.len() < LEADERBOARD_SIZE {
+            self.leaderboard.push(LeaderboardEntry { user, points });
+        } else if let Some(last) = self.leaderboard.last_mut().filter(|last| last.points < points) {
+            *last = LeaderboardEntry { user, points };
+        }
+
+        self.leaderboard.sort_by_key(|entry| Reverse(entry.points));
+    }
+}
+
+// A user's points in one season, kept apart from their lifetime `UserAccount` points.
+#[account]
+pub struct UserSeason {
+    pub season: Pubkey,
+    pub user: Pubkey,
+    pub points: u64,
+    // Points earned before the user joined don't count toward the season.
+    pub joined_at: i64,
+    pub bump: u8,
+}
+
+impl Space for UserSeason {
+    const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn season(start_time: i64, end_time: i64) -> Season {
+        Season {
+            config: Pubkey::new_unique(),
+            id: 0,
+            start_time,
+            end_time,
+            finalized: false,
+            leaderboard: Vec::new(),
+            bump: 0,
+        }
+    }
+
+    fn user_season(season: &Season, joined_at: i64) -> UserSeason {
+        UserSeason { season: season.config, user: Pubkey::new_unique(), points: 0, joined_at, bump: 0 }
+    }
+
+    #[test]
+    fn record_counts_only_points_earned_inside_the_season() {
+        let mut season = season(100, 200);
+        let mut user = user_season(&season, 0);
+
+        // Half of the 0..200 span falls inside the season
+        season.record(&mut user, 1_000, 0, 200);
+        assert_eq!(user.points, 500);
+
+        // Synced after the end, but a quarter of it was earned before the season ended
+        season.record(&mut user, 400, 150, 350);
+        assert_eq!(user.points, 600);
+
+        season.record(&mut user, 1_000, 200, 300);
+        assert_eq!(user.points, 600);
+
+        season.finalized = true;
+        season.record(&mut user, 1_000, 100, 200);
+        assert_eq!(user.points, 600);
+        assert_eq!(season.leaderboard[0].points, 600);
+    }
+
+    #[test]
+    fn record_skips_points_earned_before_joining() {
+        let mut season = season(0, 1_000);
+        let mut user = user_season(&season, 300);
+
+        season.record(&mut user, 1_000, 100, 500);
+        assert_eq!(user.points, 500);
+    }
+
+    #[test]
+    fn leaderboard_keeps_the_top_users_once_full() {
+        let mut season = season(0, 100);
+        let mut users: Vec<UserSeason> = (0..LEADERBOARD_SIZE + 2).map(|_| user_season(&season, 0)).collect();
+
+        for (points, user) in users.iter_mut().enumerate() {
+            season.record(user, points as u64 + 1, 0, 100);
+        }
+
+        assert_eq!(season.leaderboard.len(), LEADERBOARD_SIZE);
+        assert!(season.leaderboard.windows(2).all(|pair| pair[0].points > pair[1].points));
+        assert_eq!(season.leaderboard[0].user, users[LEADERBOARD_SIZE + 1].user);
+        assert!(season.leaderboard.iter().all(|entry| entry.user != users[0].user && entry.user != users[1].user));
+
+        // An existing entry moves up in place rather than taking a second slot
+        season.record(&mut users[2], 100, 0, 100);
+        assert_eq!(season.leaderboard.len(), LEADERBOARD_SIZE);
+        assert_eq!(season.leaderboard[0].user, users[2].user);
+        assert_eq!(season.leaderboard[0].points, 103);
+
+        // Too few points to beat the last entry
+        season.record(&mut users[0], 1, 0, 100);
+        assert!(season.leaderboard.iter().all(|entry| entry.user != users[0].user));
+    }
+}
\ No newline at end of file
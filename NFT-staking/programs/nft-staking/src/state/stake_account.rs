@@ -1,13 +1,69 @@
 use anchor_lang::prelude::*;
 
+use crate::{errors::StakeError, state::StakeConfig};
+
+// Multipliers are in basis points, so 10_000 is the base rate.
+pub const BASE_MULTIPLIER: u16 = 10_000;
+
 #[account]
 pub struct StakeAccount {
     pub owner: Pubkey,
     pub mint: Pubkey,
-    pub last_update: i64,
+    pub staked_at: i64,
+    pub multiplier: u16,
+    pub lock_until: i64,
+    pub lock_multiplier: u16,
+    // Share of the pool's emissions, from the rarity and lock tier multipliers.
+    pub weight: u64,
+    // Points of this stake's share already paid out to the owner.
+    pub reward_debt: u64,
+    // When the stake last accrued, so seasons can tell when its next points were earned.
+    pub last_accrued: i64,
     pub bump: u8,
 }
 
 impl Space for StakeAccount {
-    const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+    const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 2 + 8 + 2 + 8 + 8 + 8 + 1;
+}
+
+impl StakeAccount {
+    // Validates the rarity proof and lock tier against the config and adds a new stake to the
+    // pool. Shared by every staking path so they all enforce the same rules.
+    pub fn new(config: &mut StakeConfig, owner: Pubkey, mint: Pubkey, multiplier: u16, proof: &[[u8; 32]], lock_tier: u8, bump: u8) -> Result<Self> {
+        let multiplier = if config.weights_enabled() {
+            require!(config.verify_weight(&mint, multiplier, proof), StakeError::InvalidWeightProof);
+            multiplier
+        } else {
+            BASE_MULTIPLIER
+        };
+
+        let lock_tier = config.lock_tier(lock_tier).ok_or(StakeError::InvalidLockTier)?;
+
+        let now = Clock::get()?.unix_timestamp;
+
+        config.update_pool(now);
+
+        let weight = multiplier as u64 * lock_tier.multiplier as u64 / BASE_MULTIPLIER as u64;
+        config.total_weight += weight;
+
+        Ok(Self {
+            owner,
+            mint,
+            staked_at: now,
+            multiplier,
+            lock_until: now + lock_tier.duration as i64,
+            lock_multiplier: lock_tier.multiplier,
+            weight,
+            // Nothing emitted before the stake joined the pool is owed to it
+            reward_debt: config.accumulated(weight),
+            last_accrued: now,
+            bump,
+        })
+    }
+
+    // Points earned since the last accrual. The config's pool must be updated first.
+    pub fn accrue_points(&mut self, config: &StakeConfig, now: i64) -> u64 {
+        self.last_accrued = now;
+        config.settle(self.weight, &mut self.reward_debt)
+    }
 }
\ No newline at end of file
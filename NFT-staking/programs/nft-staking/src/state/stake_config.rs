@@ -1,14 +1,309 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::keccak::hashv};
+
+use super::BASE_MULTIPLIER;
+
+pub const MAX_LOCK_TIERS: usize = 4;
+
+// A lock duration stakers can pick, and the points multiplier (in basis points) it earns.
+// Tiers with a zero multiplier are unused.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct LockTier {
+    pub duration: u32,
+    pub multiplier: u16,
+}
+
+impl LockTier {
+    pub const SIZE: usize = 4 + 2;
+}
+
+// Scale of `StakeConfig::acc_points_per_weight`, so small emissions spread over a large total
+// weight don't round down to zero.
+pub const ACC_PRECISION: u128 = 1_000_000_000_000;
+
+// How the budget of each emission epoch relates to the previous one.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EmissionDecay {
+    // Every epoch emits `epoch_budget`.
+    Constant,
+    // Every epoch emits half of the previous one.
+    Halving,
+    // Every epoch emits `decay_amount` less than the previous one, down to zero.
+    Linear,
+}
+
+// Points released to all stakers combined, split between them by stake weight. Each epoch
+// releases its budget evenly over `epoch_duration` seconds, and emission stops for good once
+// `max_supply` points have been released.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct EmissionSchedule {
+    pub start_time: i64,
+    pub epoch_duration: u32,
+    pub epoch_budget: u64,
+    pub decay: EmissionDecay,
+    pub decay_amount: u64,
+    pub max_supply: u64,
+}
+
+impl EmissionSchedule {
+    pub const SIZE: usize = 8 + 4 + 8 + 1 + 8 + 8;
+
+    pub fn budget(&self, epoch: u64) -> u64 {
+        match self.decay {
+            EmissionDecay::Constant => self.epoch_budget,
+            EmissionDecay::Halving => self.epoch_budget.checked_shr(epoch.min(64) as u32).unwrap_or(0),
+            EmissionDecay::Linear => self.epoch_budget.saturating_sub(self.decay_amount.saturating_mul(epoch)),
+        }
+    }
+
+    // Total points scheduled between `start_time` and `now`, capped at `max_supply`.
+    pub fn emitted_at(&self, now: i64) -> u64 {
+        if now <= self.start_time || self.epoch_duration == 0 {
+            return 0;
+        }
+
+        let elapsed = (now - self.start_time) as u64;
+        let duration = self.epoch_duration as u64;
+        let epochs = elapsed / duration;
+        let budget = self.epoch_budget as u128;
+
+        let full_epochs = match self.decay {
+            EmissionDecay::Constant => budget * epochs as u128,
+            EmissionDecay::Halving => (0..epochs.min(64)).map(|epoch| self.budget(epoch) as u128).sum::<u128>(),
+            EmissionDecay::Linear => {
+                // Only the epochs before the budget reaches zero emit anything
+                let n = match self.decay_amount {
+                    0 => epochs,
+                    decay_amount => epochs.min(self.epoch_budget.div_ceil(decay_amount)),
+                } as u128;
+                (n * budget).saturating_sub(self.decay_amount as u128 * n * n.saturating_sub(1) / 2)
+            }
+        };
+        let current_epoch = self.budget(epochs) as u128 * (elapsed % duration) as u128 / duration as u128;
+
+        full_epochs.saturating_add(current_epoch).min(self.max_supply as u128) as u64
+    }
+}
+
+// Where claimed points are paid from: minted by the config's own rewards mint, or transferred
+// out of a vault of an existing token that the project pre-funds.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RewardMode {
+    Mint,
+    Vault,
+}
 
 #[account]
 pub struct StakeConfig {
-    pub points_per_stake: u8,
+    pub admin: Pubkey,
+    pub collection: Pubkey,
     pub max_stake: u8,
     pub freeze_period: u32,
+    // Merkle root of (mint, multiplier) leaves; all zeroes means every NFT earns the base rate.
+    pub weights_root: [u8; 32],
+    pub lock_tiers: [LockTier; MAX_LOCK_TIERS],
+    // Share of a stake's points burned when it's unstaked before its lock ends; 0 forbids it.
+    pub early_unlock_penalty_bps: u16,
+    pub emission: EmissionSchedule,
+    // Combined weight of every NFT staked under this config.
+    pub total_weight: u64,
+    // Points earned per unit of stake weight since launch, scaled by `ACC_PRECISION`.
+    pub acc_points_per_weight: u128,
+    // Scheduled points already folded into `acc_points_per_weight`. Points scheduled while
+    // nothing is staked are never distributed.
+    pub emitted: u64,
+    pub reward_mode: RewardMode,
+    pub rewards_mint: Pubkey,
+    // Fungible token that can be staked into the pool; the default key while token staking is off.
+    pub stake_mint: Pubkey,
+    // Weight of one whole staked token, on the same scale as an NFT's `BASE_MULTIPLIER`.
+    pub token_weight: u64,
+    pub token_vault_bump: u8,
+    // Set by the admin to stop accrual; NFTs can then only leave through emergency unstakes.
+    pub paused: bool,
     pub rewards_bump: u8,
     pub bump: u8,
 }
 
 impl Space for StakeConfig {
-    const INIT_SPACE: usize = 8 + 1 + 1 + 4 + 1 + 1;
+    const INIT_SPACE: usize = 8 + 32 + 32 + 1 + 4 + 32 + LockTier::SIZE * MAX_LOCK_TIERS + 2 + EmissionSchedule::SIZE + 8 + 16 + 8 + 1 + 32 + 32 + 8 + 1 + 1 + 1 + 1;
+}
+
+impl StakeConfig {
+    // Only tier 0 (no lock, base rate) is available until the admin configures more.
+    pub fn default_lock_tiers() -> [LockTier; MAX_LOCK_TIERS] {
+        let mut lock_tiers = [LockTier::default(); MAX_LOCK_TIERS];
+        lock_tiers[0].multiplier = BASE_MULTIPLIER;
+        lock_tiers
+    }
+
+    pub fn lock_tier(&self, index: u8) -> Option<LockTier> {
+        self.lock_tiers
+            .get(index as usize)
+            .copied()
+            .filter(|tier| tier.multiplier > 0)
+    }
+
+    // Spreads the points scheduled since the last update over the current total weight. Must run
+    // before any stake joins or leaves the pool, or before reading `acc_points_per_weight`.
+    // Points scheduled while paused are dropped.
+    pub fn update_pool(&mut self, now: i64) {
+        let emitted = self.emission.emitted_at(now);
+
+        if self.total_weight > 0 && !self.paused {
+            self.acc_points_per_weight += emitted.saturating_sub(self.emitted) as u128 * ACC_PRECISION / self.total_weight as u128;
+        }
+
+        self.emitted = emitted.max(self.emitted);
+    }
+
+    // Points a stake of `weight` has earned over the whole life of the pool.
+    pub fn accumulated(&self, weight: u64) -> u64 {
+        (weight as u128 * self.acc_points_per_weight / ACC_PRECISION) as u64
+    }
+
+    // Points a stake of `weight` earned since its `reward_debt` was last settled, moving the
+    // debt up to match. Shared by NFT and token stakes.
+    pub fn settle(&self, weight: u64, reward_debt: &mut u64) -> u64 {
+        let accumulated = self.accumulated(weight);
+        let points = accumulated - *reward_debt;
+        *reward_debt = accumulated;
+        points
+    }
+
+    pub fn token_staking_enabled(&self) -> bool {
+        self.stake_mint != Pubkey::default()
+    }
+
+    pub fn weights_enabled(&self) -> bool {
+        self.weights_root != [0u8; 32]
+    }
+
+    // Leaves are keccak(mint || multiplier_le) and pairs are hashed in sorted order.
+    pub fn verify_weight(&self, mint: &Pubkey, multiplier: u16, proof: &[[u8; 32]]) -> bool {
+        let mut node = hashv(&[mint.as_ref(), multiplier.to_le_bytes().as_ref()]).to_bytes();
+
+        for sibling in proof {
+            node = if node <= *sibling {
+                hashv(&[node.as_ref(), sibling.as_ref()]).to_bytes()
+            } else {
+                hashv(&[sibling.as_ref(), node.as_ref()]).to_bytes()
+            };
+        }
+
+        node == self.weights_root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(mint: &Pubkey, multiplier: u16) -> [u8; 32] {
+        hashv(&[mint.as_ref(), multiplier.to_le_bytes().as_ref()]).to_bytes()
+    }
+
+    fn parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[left.as_ref(), right.as_ref()]).to_bytes()
+    }
+
+    fn config(weights_root: [u8; 32]) -> StakeConfig {
+        StakeConfig {
+            admin: Pubkey::new_unique(),
+            collection: Pubkey::new_unique(),
+            max_stake: 1,
+            freeze_period: 0,
+            weights_root,
+            lock_tiers: StakeConfig::default_lock_tiers(),
+            early_unlock_penalty_bps: 0,
+            emission: EmissionSchedule {
+                start_time: 0,
+                epoch_duration: 1,
+                epoch_budget: 10,
+                decay: EmissionDecay::Constant,
+                decay_amount: 0,
+                max_supply: u64::MAX,
+            },
+            total_weight: 0,
+            acc_points_per_weight: 0,
+            emitted: 0,
+            reward_mode: RewardMode::Mint,
+            rewards_mint: Pubkey::new_unique(),
+            stake_mint: Pubkey::default(),
+            token_weight: 0,
+            token_vault_bump: 0,
+            paused: false,
+            rewards_bump: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn verify_weight_checks_proofs_of_a_sorted_pair_tree() {
+        let mints = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let multipliers = [10_000, 12_500, 15_000, 20_000];
+        let leaves: Vec<[u8; 32]> = mints.iter().zip(multipliers).map(|(mint, multiplier)| leaf(mint, multiplier)).collect();
+
+        let left = parent(leaves[0], leaves[1]);
+        let right = parent(leaves[2], leaves[3]);
+        let config = config(parent(left, right));
+
+        let proofs = [
+            vec![leaves[1], right],
+            vec![leaves[0], right],
+            vec![leaves[3], left],
+            vec![leaves[2], left],
+        ];
+        for ((mint, multiplier), proof) in mints.iter().zip(multipliers).zip(&proofs) {
+            assert!(config.verify_weight(mint, multiplier, proof));
+        }
+
+        // A different multiplier, another leaf's proof, a partial proof or an unknown mint
+        assert!(!config.verify_weight(&mints[0], 20_000, &proofs[0]));
+        assert!(!config.verify_weight(&mints[0], multipliers[0], &proofs[1]));
+        assert!(!config.verify_weight(&mints[0], multipliers[0], &proofs[0][..1]));
+        assert!(!config.verify_weight(&Pubkey::new_unique(), multipliers[0], &proofs[0]));
+    }
+
+    fn schedule(epoch_budget: u64, decay: EmissionDecay, decay_amount: u64, max_supply: u64) -> EmissionSchedule {
+        EmissionSchedule { start_time: 1_000, epoch_duration: 10, epoch_budget, decay, decay_amount, max_supply }
+    }
+
+    #[test]
+    fn halving_stops_emitting_past_64_epochs() {
+        let emission = schedule(1024, EmissionDecay::Halving, 0, u64::MAX);
+
+        // Epoch 0 plus half of epoch 1's 512 budget
+        assert_eq!(emission.emitted_at(1_015), 1024 + 256);
+        // 1024 + 512 + ... + 1, after which every epoch's budget is zero
+        assert_eq!(emission.emitted_at(1_000 + 64 * 10), 2047);
+        assert_eq!(emission.emitted_at(1_000 + 1_000 * 10 + 5), 2047);
+        assert_eq!(emission.emitted_at(i64::MAX), 2047);
+
+        // The sum of a full budget's halvings exceeds u64 but is capped, not overflowed
+        let emission = schedule(u64::MAX, EmissionDecay::Halving, 0, u64::MAX);
+        assert_eq!(emission.emitted_at(1_000 + 64 * 10), u64::MAX);
+    }
+
+    #[test]
+    fn linear_decay_stops_once_the_budget_reaches_zero() {
+        // Epochs emit 10, 6, 2 and then nothing
+        let emission = schedule(10, EmissionDecay::Linear, 4, u64::MAX);
+
+        assert_eq!(emission.emitted_at(1_015), 10 + 3);
+        assert_eq!(emission.emitted_at(1_025), 10 + 6 + 1);
+        assert_eq!(emission.emitted_at(1_030), 18);
+        assert_eq!(emission.emitted_at(1_035), 18);
+        assert_eq!(emission.emitted_at(1_000 + 1_000 * 10), 18);
+    }
+
+    #[test]
+    fn emission_is_capped_at_max_supply() {
+        let emission = schedule(10, EmissionDecay::Constant, 0, 25);
+
+        assert_eq!(emission.emitted_at(1_000), 0);
+        assert_eq!(emission.emitted_at(1_020), 20);
+        assert_eq!(emission.emitted_at(1_025), 25);
+        assert_eq!(emission.emitted_at(i64::MAX), 25);
+    }
 }
\ No newline at end of file
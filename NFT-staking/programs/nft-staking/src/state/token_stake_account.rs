@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::state::StakeConfig;
+
+// A user's fungible token stake. It sits in the same emission pool as staked NFTs, with a
+// weight proportional to the amount deposited, so points accrue with amount and time staked.
+#[account]
+pub struct TokenStakeAccount {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub weight: u64,
+    pub reward_debt: u64,
+    // When the stake last accrued, so seasons can tell when its next points were earned.
+    pub last_accrued: i64,
+    pub bump: u8,
+}
+
+impl Space for TokenStakeAccount {
+    const INIT_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1;
+}
+
+impl TokenStakeAccount {
+    // Points earned since the last accrual. The config's pool must be updated first.
+    pub fn accrue_points(&mut self, config: &StakeConfig, now: i64) -> u64 {
+        self.last_accrued = now;
+        config.settle(self.weight, &mut self.reward_debt)
+    }
+
+    // Moves the stake to a new amount, and its weight in the pool with it. Pending points must
+    // be accrued first since the reward debt is reset to the new weight.
+    pub fn set_amount(&mut self, config: &mut StakeConfig, amount: u64, decimals: u8) {
+        config.total_weight -= self.weight;
+
+        self.amount = amount;
+        self.weight = (amount as u128 * config.token_weight as u128 / 10_u128.pow(decimals as u32)) as u64;
+        self.reward_debt = config.accumulated(self.weight);
+
+        config.total_weight += self.weight;
+    }
+}
\ No newline at end of file
@@ -1,12 +1,93 @@
 use anchor_lang::prelude::*;
 
+use crate::{errors::StakeError, state::{Season, StakeAccount, StakeConfig, UserSeason, BASE_MULTIPLIER}};
+
+// Upper bound on `StakeConfig::max_stake`, so a user's staked mints always fit their account.
+pub const MAX_STAKED_MINTS: usize = 16;
+
 #[account]
 pub struct UserAccount {
-    pub points: u32,
+    pub points: u64,
     pub amount_staked: u8,
+    // Mints (or asset ids, for compressed NFTs) this user currently has staked.
+    pub staked_mints: Vec<Pubkey>,
+    // Lifetime counters, never reset by claims or unstakes.
+    pub total_points_earned: u64,
+    pub total_staked_seconds: u64,
     pub bump: u8,
 }
 
 impl Space for UserAccount {
-    const INIT_SPACE: usize = 8 + 4 + 1 + 1;
+    const INIT_SPACE: usize = 8 + 8 + 1 + 4 + 32 * MAX_STAKED_MINTS + 8 + 8 + 1;
+}
+
+impl UserAccount {
+    pub fn add_points(&mut self, points: u64) {
+        self.points += points;
+        self.total_points_earned += points;
+    }
+
+    pub fn record_stake(&mut self, config: &StakeConfig, mint: Pubkey) -> Result<()> {
+        require!(self.amount_staked < config.max_stake, StakeError::MaxStakeReached);
+        self.amount_staked += 1;
+        self.staked_mints.push(mint);
+        Ok(())
+    }
+
+    // Checks the freeze period, pays out the points the stake has accrued, applies the early
+    // unlock penalty if the stake is still locked and takes the stake out of the pool. The points
+    // also count toward the user's season, if one is passed.
+    pub fn record_unstake(
+        &mut self,
+        config: &mut StakeConfig,
+        stake_account: &mut StakeAccount,
+        season: Option<(&mut Season, &mut UserSeason)>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(!config.paused, StakeError::Paused);
+        require!(now - stake_account.staked_at >= config.freeze_period as i64, StakeError::FreezePeriodNotPassed);
+
+        config.update_pool(now);
+        let since = stake_account.last_accrued;
+        let mut points = stake_account.accrue_points(config, now);
+        config.total_weight -= stake_account.weight;
+
+        // Leaving a lock early is only allowed when the config sets a penalty, which burns that
+        // share of the points this stake earned. Claims skip locked stakes, so that's everything
+        // it earned since it was staked.
+        if now < stake_account.lock_until {
+            require!(config.early_unlock_penalty_bps > 0, StakeError::LockNotExpired);
+
+            points -= points
+                .checked_mul(config.early_unlock_penalty_bps as u64)
+                .ok_or(StakeError::Overflow)?
+                / BASE_MULTIPLIER as u64;
+        }
+
+        if let Some((season, user_season)) = season {
+            season.record(user_season, points, since, now);
+        }
+
+        self.add_points(points);
+        self.remove_stake(stake_account, now);
+        Ok(())
+    }
+
+    // Takes a stake out of the pool while the config is paused, skipping the freeze period,
+    // lock and points accrual. Points the stake hasn't accrued yet are forfeited.
+    pub fn record_emergency_unstake(&mut self, config: &mut StakeConfig, stake_account: &StakeAccount) -> Result<()> {
+        require!(config.paused, StakeError::NotPaused);
+
+        config.total_weight -= stake_account.weight;
+
+        self.remove_stake(stake_account, Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+
+    fn remove_stake(&mut self, stake_account: &StakeAccount, now: i64) {
+        self.amount_staked -= 1;
+        self.staked_mints.retain(|mint| *mint != stake_account.mint);
+        self.total_staked_seconds += (now - stake_account.staked_at) as u64;
+    }
 }
\ No newline at end of file
@@ -0,0 +1,284 @@
+// Runs against the compiled program: `cargo test-sbf` builds it and enables this suite.
+#![cfg(feature = "test-sbf")]
+
+use anchor_lang::{
+    prelude::borsh::BorshSerialize, solana_program::program_pack::Pack, system_program,
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    metadata::mpl_token_metadata::{
+        self,
+        accounts::{MasterEdition, Metadata},
+        types::{Collection, Key},
+    },
+    token::spl_token,
+};
+use litesvm::LiteSVM;
+use nft_staking::{EmissionDecay, EmissionSchedule, StakeError};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    pubkey,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+
+const PROGRAM: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy/nft_staking.so");
+const MEMO_V1: Pubkey = pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+
+const FREEZE_PERIOD: u32 = 60;
+
+struct Test {
+    svm: LiteSVM,
+    user: Keypair,
+    collection: Pubkey,
+    config: Pubkey,
+}
+
+impl Test {
+    fn new(max_stake: u8) -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(nft_staking::ID, PROGRAM).unwrap();
+
+        // Token Metadata isn't available as a binary here. Memo v1 accepts the freeze and thaw
+        // CPIs as no-ops, which is all the staking limits need from it.
+        let memo = svm.get_account(&MEMO_V1).unwrap();
+        svm.set_account(mpl_token_metadata::ID, memo).unwrap();
+
+        let user = Keypair::new();
+        svm.airdrop(&user.pubkey(), 10_000_000_000).unwrap();
+
+        let collection = Pubkey::new_unique();
+        let config = Pubkey::find_program_address(&[b"config", collection.as_ref()], &nft_staking::ID).0;
+
+        let mut test = Self { svm, user, collection, config };
+        test.set_mint(collection);
+        test.set_metadata(collection, None);
+
+        let initialize_config = Instruction {
+            program_id: nft_staking::ID,
+            accounts: nft_staking::accounts::InitializeConfig {
+                admin: test.user.pubkey(),
+                collection,
+                config,
+                rewards_mint: Pubkey::find_program_address(&[b"rewards", config.as_ref()], &nft_staking::ID).0,
+                metadata: Metadata::find_pda(&collection).0,
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                metadata_program: mpl_token_metadata::ID,
+            }
+            .to_account_metas(None),
+            data: nft_staking::instruction::InitializeConfig {
+                max_stake,
+                freeze_period: FREEZE_PERIOD,
+                emission: EmissionSchedule {
+                    start_time: 0,
+                    epoch_duration: 86_400,
+                    epoch_budget: 1_000_000,
+                    decay: EmissionDecay::Constant,
+                    decay_amount: 0,
+                    max_supply: u64::MAX,
+                },
+            }
+            .data(),
+        };
+        let initialize_user = Instruction {
+            program_id: nft_staking::ID,
+            accounts: nft_staking::accounts::Initialize {
+                user: test.user.pubkey(),
+                config,
+                user_account: test.user_account(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: nft_staking::instruction::InitializeUser {}.data(),
+        };
+        test.send(initialize_config).unwrap();
+        test.send(initialize_user).unwrap();
+
+        test
+    }
+
+    fn user_account(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"user", self.config.as_ref(), self.user.pubkey().as_ref()], &nft_staking::ID).0
+    }
+
+    fn stake_account(&self, mint: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"stake", mint.as_ref(), self.config.as_ref()], &nft_staking::ID).0
+    }
+
+    fn send(&mut self, instruction: Instruction) -> Result<(), TransactionError> {
+        self.svm.expire_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.user.pubkey()),
+            &[&self.user],
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(tx).map(|_| ()).map_err(|failed| failed.err)
+    }
+
+    fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    fn set_account(&mut self, address: Pubkey, owner: Pubkey, data: Vec<u8>) {
+        let account = Account {
+            lamports: self.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.svm.set_account(address, account).unwrap();
+    }
+
+    fn set_mint(&mut self, mint: Pubkey) {
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: Some(self.user.pubkey()).into(),
+            supply: 1,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: Some(self.user.pubkey()).into(),
+        }
+        .pack_into_slice(&mut data);
+        self.set_account(mint, spl_token::ID, data);
+    }
+
+    // Metadata updatable by the user, so the user can also create the collection's config.
+    fn set_metadata(&mut self, mint: Pubkey, collection: Option<Collection>) {
+        let metadata = Metadata {
+            key: Key::MetadataV1,
+            update_authority: self.user.pubkey(),
+            mint,
+            name: "GM".to_string(),
+            symbol: String::new(),
+            uri: String::new(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: None,
+            collection,
+            uses: None,
+            collection_details: None,
+            programmable_config: None,
+        };
+        self.set_account(Metadata::find_pda(&mint).0, mpl_token_metadata::ID, metadata.try_to_vec().unwrap());
+    }
+
+    // An NFT of the collection held by the user, with verified metadata and a master edition.
+    fn mint_nft(&mut self) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        self.set_mint(mint);
+
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner: self.user.pubkey(),
+            amount: 1,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        self.set_account(get_associated_token_address(&self.user.pubkey(), &mint), spl_token::ID, data);
+
+        self.set_metadata(mint, Some(Collection { verified: true, key: self.collection }));
+
+        let edition = MasterEdition { key: Key::MasterEditionV2, supply: 0, max_supply: Some(0) };
+        self.set_account(MasterEdition::find_pda(&mint).0, mpl_token_metadata::ID, edition.try_to_vec().unwrap());
+
+        mint
+    }
+
+    fn stake(&mut self, mint: Pubkey) -> Result<(), TransactionError> {
+        let instruction = Instruction {
+            program_id: nft_staking::ID,
+            accounts: nft_staking::accounts::Stake {
+                user: self.user.pubkey(),
+                mint,
+                collection: self.collection,
+                mint_ata: get_associated_token_address(&self.user.pubkey(), &mint),
+                metadata: Metadata::find_pda(&mint).0,
+                edition: MasterEdition::find_pda(&mint).0,
+                config: self.config,
+                stake_account: self.stake_account(mint),
+                user_account: self.user_account(),
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                metadata_program: mpl_token_metadata::ID,
+            }
+            .to_account_metas(None),
+            data: nft_staking::instruction::Stake { multiplier: 10_000, proof: vec![], lock_tier: 0 }.data(),
+        };
+        self.send(instruction)
+    }
+
+    fn unstake(&mut self, mint: Pubkey) -> Result<(), TransactionError> {
+        let instruction = Instruction {
+            program_id: nft_staking::ID,
+            accounts: nft_staking::accounts::Unstake {
+                user: self.user.pubkey(),
+                mint,
+                mint_ata: get_associated_token_address(&self.user.pubkey(), &mint),
+                metadata: Metadata::find_pda(&mint).0,
+                edition: MasterEdition::find_pda(&mint).0,
+                config: self.config,
+                stake_account: self.stake_account(mint),
+                user_account: self.user_account(),
+                season_entry: nft_staking::accounts::SeasonEntry { season: None, user_season: None },
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                metadata_program: mpl_token_metadata::ID,
+            }
+            .to_account_metas(None),
+            data: nft_staking::instruction::Unstake {}.data(),
+        };
+        self.send(instruction)
+    }
+}
+
+fn assert_error(result: Result<(), TransactionError>, error: StakeError) {
+    assert_eq!(result, Err(TransactionError::InstructionError(0, InstructionError::Custom(error.into()))));
+}
+
+#[test]
+fn stake_fails_once_max_stake_is_reached() {
+    let mut test = Test::new(2);
+    let mints = [test.mint_nft(), test.mint_nft(), test.mint_nft()];
+
+    test.stake(mints[0]).unwrap();
+    test.stake(mints[1]).unwrap();
+    assert_error(test.stake(mints[2]), StakeError::MaxStakeReached);
+
+    // Unstaking frees up a slot
+    test.warp_to(FREEZE_PERIOD as i64);
+    test.unstake(mints[0]).unwrap();
+    test.stake(mints[2]).unwrap();
+}
+
+#[test]
+fn unstake_fails_until_freeze_period_has_passed() {
+    let mut test = Test::new(1);
+    let mint = test.mint_nft();
+
+    test.warp_to(1_000);
+    test.stake(mint).unwrap();
+
+    assert_error(test.unstake(mint), StakeError::FreezePeriodNotPassed);
+
+    test.warp_to(1_000 + FREEZE_PERIOD as i64 - 1);
+    assert_error(test.unstake(mint), StakeError::FreezePeriodNotPassed);
+
+    test.warp_to(1_000 + FREEZE_PERIOD as i64);
+    test.unstake(mint).unwrap();
+    assert!(test.svm.get_account(&test.stake_account(mint)).is_none_or(|account| account.lamports == 0));
+}
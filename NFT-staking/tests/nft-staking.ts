@@ -1,12 +1,15 @@
 import * as anchor from "@coral-xyz/anchor";
-import { createNft, findMasterEditionPda, findMetadataPda, mplTokenMetadata, verifyCollection, verifySizedCollectionItem } from '@metaplex-foundation/mpl-token-metadata'
+import { createNft, createProgrammableNft, findMasterEditionPda, findMetadataPda, findTokenRecordPda, mplTokenMetadata, verifyCollection, verifyCollectionV1, verifySizedCollectionItem } from '@metaplex-foundation/mpl-token-metadata'
 import { createUmi } from "@metaplex-foundation/umi-bundle-defaults"
-import { KeypairSigner, PublicKey, createSignerFromKeypair, generateSigner, keypairIdentity, percentAmount } from '@metaplex-foundation/umi';
+import { MetadataArgsArgs, TokenProgramVersion, TokenStandard, createTree, fetchMerkleTree, findLeafAssetIdPda, findTreeConfigPda, getCurrentRoot, getMerkleProof, getMerkleRoot, getMetadataArgsSerializer, hashLeaf, hashMetadataCreators, hashMetadataData, mintToCollectionV1, mplBubblegum, MPL_BUBBLEGUM_PROGRAM_ID, SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, SPL_NOOP_PROGRAM_ID } from "@metaplex-foundation/mpl-bubblegum"
+import { KeypairSigner, PublicKey, createSignerFromKeypair, generateSigner, keypairIdentity, none, percentAmount, publicKey, publicKeyBytes, some } from '@metaplex-foundation/umi';
 import { Program } from "@coral-xyz/anchor";
 import { NftStaking } from "../target/types/nft_staking";
-import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID, createMint, getAssociatedTokenAddressSync, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID, createMint, getAccount, getAssociatedTokenAddressSync, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
 import { SYSTEM_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/native/system";
+import { assert } from "chai";
+import { keccak_256 } from "@noble/hashes/sha3";
 
 describe("nft-staking", () => {
   // Configure the client to use the local cluster.
@@ -20,7 +23,9 @@ describe("nft-staking", () => {
   const payer = provider.wallet as NodeWallet;
 
   let nftMint: KeypairSigner;
+  let secondNftMint: KeypairSigner;
   let collectionMint: KeypairSigner;
+  let pnftMint: KeypairSigner;
 
   let stakeAccount: anchor.web3.PublicKey;
 
@@ -28,12 +33,12 @@ describe("nft-staking", () => {
   const creator = createSignerFromKeypair(umi, creatorWallet);
   umi.use(keypairIdentity(creator));
   umi.use(mplTokenMetadata());
+  umi.use(mplBubblegum());
 
-  const config = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId)[0];
-
-  const rewardsMint = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("rewards"), config.toBuffer()], program.programId)[0];
-
-  const userAccount = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("user"), provider.publicKey.toBuffer()], program.programId)[0];
+  // Configs are keyed by collection mint, so these are derived once the collection exists
+  let config: anchor.web3.PublicKey;
+  let rewardsMint: anchor.web3.PublicKey;
+  let userAccount: anchor.web3.PublicKey;
 
   stakeAccount = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("stake"), provider.publicKey.toBuffer()], program.programId)[0];
   it("Mint Collection NFT", async () => {
@@ -50,6 +55,13 @@ describe("nft-staking", () => {
             }
         }).sendAndConfirm(umi)
         console.log(`Created Collection NFT: ${collectionMint.publicKey.toString()}`)
+
+    config = anchor.web3.PublicKey.findProgramAddressSync([
+      Buffer.from("config"),
+      new anchor.web3.PublicKey(collectionMint.publicKey as PublicKey).toBuffer()
+    ], program.programId)[0];
+    rewardsMint = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("rewards"), config.toBuffer()], program.programId)[0];
+    userAccount = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("user"), config.toBuffer(), provider.publicKey.toBuffer()], program.programId)[0];
   });
 
   it("Mint NFT", async () => {
@@ -81,10 +93,67 @@ describe("nft-staking", () => {
     console.log("\nCollection NFT Verified!")
   });
 
+  it("Mint and verify second NFT", async () => {
+    secondNftMint = generateSigner(umi);
+        await createNft(umi, {
+            mint: secondNftMint,
+            name: "GM",
+            symbol: "GM",
+            uri: "https://arweave.net/123",
+            sellerFeeBasisPoints: percentAmount(5.5),
+            collection: {verified: false, key: collectionMint.publicKey},
+            creators: null,
+        }).sendAndConfirm(umi)
+
+    await verifySizedCollectionItem(umi, {
+      metadata: findMetadataPda(umi, {mint: secondNftMint.publicKey}),
+      collectionAuthority: creator,
+      collectionMint: collectionMint.publicKey,
+      collection: findMetadataPda(umi, {mint: collectionMint.publicKey}),
+      collectionMasterEditionAccount: findMasterEditionPda(umi, {mint: collectionMint.publicKey}),
+     }).sendAndConfirm(umi)
+    console.log(`\nCreated NFT: ${secondNftMint.publicKey.toString()}`)
+  });
+
+  // 10 points a second shared between stakers from now on, capped at 100_000 points
+  const emission = {
+    startTime: new anchor.BN(Math.floor(Date.now() / 1000)),
+    epochDuration: 1,
+    epochBudget: new anchor.BN(10),
+    decay: { constant: {} },
+    decayAmount: new anchor.BN(0),
+    maxSupply: new anchor.BN(100_000),
+  };
+
+  it("Only the collection's update authority can initialize its config", async () => {
+    const stranger = anchor.web3.Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(stranger.publicKey, anchor.web3.LAMPORTS_PER_SOL),
+    );
+
+    try {
+      await program.methods.initializeConfig(2, 2, emission)
+      .accountsPartial({
+        admin: stranger.publicKey,
+        collection: collectionMint.publicKey,
+        metadata: new anchor.web3.PublicKey(findMetadataPda(umi, {mint: collectionMint.publicKey})[0]),
+        config,
+        rewardsMint,
+      })
+      .signers([stranger])
+      .rpc();
+      assert.fail("only the update authority can create the config");
+    } catch (err) {
+      assert.include(err.toString(), "NotCollectionAuthority");
+    }
+  });
+
   it("Initialize Config Account", async () => {
-    const tx = await program.methods.initializeConfig(10, 10, 0)
+    const tx = await program.methods.initializeConfig(2, 2, emission)
     .accountsPartial({
       admin: provider.wallet.publicKey,
+      collection: collectionMint.publicKey,
+      metadata: new anchor.web3.PublicKey(findMetadataPda(umi, {mint: collectionMint.publicKey})[0]),
       config,
       rewardsMint,
       systemProgram: anchor.web3.SystemProgram.programId,
@@ -95,10 +164,37 @@ describe("nft-staking", () => {
     console.log("Your transaction signature", tx);
   });
 
+  // max_stake = 1 and a 2 second freeze period so both limits can be hit below
+  it("Update Config Account", async () => {
+    await program.methods.updateConfig(1, 2)
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config,
+    })
+    .rpc();
+
+    const account = await program.account.stakeConfig.fetch(config);
+    assert.equal(account.maxStake, 1);
+  });
+
+  it("Reject invalid emission schedules", async () => {
+    for (const schedule of [{ ...emission, epochDuration: 0 }, { ...emission, maxSupply: new anchor.BN(0) }]) {
+      try {
+        await program.methods.setEmission(schedule)
+        .accountsPartial({ admin: provider.wallet.publicKey, config })
+        .rpc();
+        assert.fail("invalid schedules should be rejected");
+      } catch (err) {
+        assert.include(err.toString(), "InvalidEmissionSchedule");
+      }
+    }
+  });
+
   it("Initialize User Account", async() => {
     const tx = await program.methods.initializeUser()
     .accountsPartial({
       user: provider.wallet.publicKey,
+      config,
       userAccount,
       systemProgram: anchor.web3.SystemProgram.programId,
     })
@@ -119,7 +215,7 @@ describe("nft-staking", () => {
       config.toBuffer()
     ], program.programId)[0];
 
-    const tx = await program.methods.stake()
+    const tx = await program.methods.stake(10_000, [], 0)
     .accountsPartial({
       user: provider.wallet.publicKey,
       mint: nftMint.publicKey,
@@ -135,6 +231,115 @@ describe("nft-staking", () => {
 
     console.log("\nNFT Staked!");
     console.log("Your transaction signature", tx);
+
+    const account = await program.account.userAccount.fetch(userAccount);
+    assert.ok(account.stakedMints[0].equals(new anchor.web3.PublicKey(nftMint.publicKey as PublicKey)));
+  })
+
+  it("Stake beyond max stake fails", async() => {
+    const mint = new anchor.web3.PublicKey(secondNftMint.publicKey as PublicKey);
+
+    let failed = false;
+    try {
+      await program.methods.stake(10_000, [], 0)
+      .accountsPartial({
+        user: provider.wallet.publicKey,
+        mint,
+        collection: collectionMint.publicKey,
+        mintAta: getAssociatedTokenAddressSync(mint, provider.wallet.publicKey),
+        metadata: new anchor.web3.PublicKey(findMetadataPda(umi, {mint: secondNftMint.publicKey})[0]),
+        edition: new anchor.web3.PublicKey(findMasterEditionPda(umi, {mint: secondNftMint.publicKey})[0]),
+        config,
+        stakeAccount: anchor.web3.PublicKey.findProgramAddressSync([
+          Buffer.from("stake"),
+          mint.toBuffer(),
+          config.toBuffer()
+        ], program.programId)[0],
+        userAccount,
+      })
+      .rpc();
+    } catch (e) {
+      failed = true;
+      assert.equal((e as anchor.AnchorError).error.errorCode.code, "MaxStakeReached");
+    }
+    assert.isTrue(failed);
+  })
+
+  it("Unstake before freeze period fails", async() => {
+    const mintAta = getAssociatedTokenAddressSync(new anchor.web3.PublicKey(nftMint.publicKey as PublicKey), provider.wallet.publicKey);
+
+    let failed = false;
+    try {
+      await program.methods.unstake()
+      .accountsPartial({
+        user: provider.wallet.publicKey,
+        mint: nftMint.publicKey,
+        mintAta,
+        metadata: new anchor.web3.PublicKey(findMetadataPda(umi, {mint: nftMint.publicKey})[0]),
+        edition: new anchor.web3.PublicKey(findMasterEditionPda(umi, {mint: nftMint.publicKey})[0]),
+        config,
+        stakeAccount,
+        userAccount,
+        seasonEntry: { season: null, userSeason: null },
+      })
+      .rpc();
+    } catch (e) {
+      failed = true;
+      assert.equal((e as anchor.AnchorError).error.errorCode.code, "FreezePeriodNotPassed");
+    }
+    assert.isTrue(failed);
+
+    // let the freeze period run out for the unstake below
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+  })
+
+  it("Claim while staked", async() => {
+    const rewardsAta = getAssociatedTokenAddressSync(rewardsMint, provider.wallet.publicKey);
+
+    const rewardsBalance = async () => {
+      const info = await provider.connection.getAccountInfo(rewardsAta);
+      return info ? Number((await getAccount(provider.connection, rewardsAta)).amount) : 0;
+    };
+
+    const balanceBefore = await rewardsBalance();
+    const userBefore = await program.account.userAccount.fetch(userAccount);
+    const stakeBefore = await program.account.stakeAccount.fetch(stakeAccount);
+
+    // let some points accrue to the stake
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const tx = await program.methods.claim()
+    .accountsPartial({
+      user: provider.wallet.publicKey,
+      userAccount,
+      rewardsMint,
+      config,
+      rewardsAta,
+      tokenStake: null,
+      seasonEntry: { season: null, userSeason: null },
+      rewardVault: null,
+      systemProgram: SYSTEM_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .remainingAccounts([{ pubkey: stakeAccount, isSigner: false, isWritable: true }])
+    .rpc();
+
+    console.log("\nRewards claimed without unstaking");
+    console.log("Your transaction signature", tx);
+
+    // the NFT stays staked, with its accrued points paid out
+    const stakeAfter = await program.account.stakeAccount.fetch(stakeAccount);
+    assert.isTrue(stakeAfter.rewardDebt.gt(stakeBefore.rewardDebt));
+
+    const userAfter = await program.account.userAccount.fetch(userAccount);
+    const earned = userAfter.totalPointsEarned.sub(userBefore.totalPointsEarned).toNumber();
+    assert.isAbove(earned, 0);
+    assert.equal(userAfter.points.toNumber(), 0);
+
+    // every unclaimed point is minted as one whole rewards token (6 decimals)
+    const minted = (await rewardsBalance()) - balanceBefore;
+    assert.equal(minted, (userBefore.points.toNumber() + earned) * 1_000_000);
   })
 
   it("Unstake NFT", async() => {
@@ -159,6 +364,7 @@ describe("nft-staking", () => {
       config,
       stakeAccount,
       userAccount,
+      seasonEntry: { season: null, userSeason: null },
     })
     .rpc();
 
@@ -167,6 +373,10 @@ describe("nft-staking", () => {
 
     let account = await program.account.userAccount.fetch(userAccount)
     console.log("user points: ", account.points);
+
+    assert.equal(account.stakedMints.length, 0);
+    assert.isAbove(account.totalStakedSeconds.toNumber(), 0);
+    assert.isAbove(account.totalPointsEarned.toNumber(), 0);
   })
 
   it("Claim Rewards", async() => {
@@ -179,6 +389,9 @@ describe("nft-staking", () => {
       rewardsMint,
       config,
       rewardsAta,
+      tokenStake: null,
+      seasonEntry: { season: null, userSeason: null },
+      rewardVault: null,
       systemProgram: SYSTEM_PROGRAM_ID,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -190,5 +403,839 @@ describe("nft-staking", () => {
 
     let account = await program.account.userAccount.fetch(userAccount)
     console.log("user points: ", account.points);
+
+    // the only staker received the whole emission, which never exceeds the max supply
+    const minted = Number((await getAccount(provider.connection, rewardsAta)).amount);
+    assert.isAbove(minted, 0);
+    assert.isAtMost(minted, emission.maxSupply.toNumber() * 10 ** 6);
+  })
+
+  // Leaves are keccak(mint || multiplier as u16 LE) and pairs are hashed in sorted order
+  const weightLeaf = (mint: anchor.web3.PublicKey, multiplier: number) => {
+    const multiplierBytes = Buffer.alloc(2);
+    multiplierBytes.writeUInt16LE(multiplier);
+    return Buffer.from(keccak_256(Buffer.concat([mint.toBuffer(), multiplierBytes])));
+  };
+  const hashPair = (a: Buffer, b: Buffer) =>
+    Buffer.from(keccak_256(Buffer.compare(a, b) <= 0 ? Buffer.concat([a, b]) : Buffer.concat([b, a])));
+
+  const nftAccounts = (mint: KeypairSigner) => {
+    const mintKey = new anchor.web3.PublicKey(mint.publicKey as PublicKey);
+    return {
+      user: provider.wallet.publicKey,
+      mint: mintKey,
+      mintAta: getAssociatedTokenAddressSync(mintKey, provider.wallet.publicKey),
+      metadata: new anchor.web3.PublicKey(findMetadataPda(umi, {mint: mint.publicKey})[0]),
+      edition: new anchor.web3.PublicKey(findMasterEditionPda(umi, {mint: mint.publicKey})[0]),
+      config,
+      stakeAccount: anchor.web3.PublicKey.findProgramAddressSync([
+        Buffer.from("stake"),
+        mintKey.toBuffer(),
+        config.toBuffer()
+      ], program.programId)[0],
+      userAccount,
+    };
+  };
+
+  // The second NFT is rare and earns 1.5x; the first one is listed at the base rate
+  const rareLeaf = () => weightLeaf(new anchor.web3.PublicKey(secondNftMint.publicKey as PublicKey), 15_000);
+  const baseLeaf = () => weightLeaf(new anchor.web3.PublicKey(nftMint.publicKey as PublicKey), 10_000);
+
+  it("Stake with a rarity multiplier proof", async() => {
+    await program.methods.setWeightsRoot(Array.from(hashPair(rareLeaf(), baseLeaf())))
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config,
+    })
+    .rpc();
+
+    const accounts = nftAccounts(secondNftMint);
+
+    const tx = await program.methods.stake(15_000, [Array.from(baseLeaf())], 0)
+    .accountsPartial({ ...accounts, collection: collectionMint.publicKey })
+    .rpc();
+    console.log("\nRare NFT Staked!");
+    console.log("Your transaction signature", tx);
+
+    const stake = await program.account.stakeAccount.fetch(accounts.stakeAccount);
+    assert.equal(stake.multiplier, 15_000);
+    assert.equal(stake.weight.toNumber(), 15_000);
+  })
+
+  it("Stake with an invalid multiplier proof fails", async() => {
+    const accounts = nftAccounts(nftMint);
+
+    let failed = false;
+    try {
+      // claims 2x with a proof for a different leaf
+      await program.methods.stake(20_000, [Array.from(rareLeaf())], 0)
+      .accountsPartial({ ...accounts, collection: collectionMint.publicKey })
+      .rpc();
+    } catch (e) {
+      failed = true;
+      assert.equal((e as anchor.AnchorError).error.errorCode.code, "InvalidWeightProof");
+    }
+    assert.isTrue(failed);
+
+    // unstake the rare NFT once its freeze period is over and go back to base rates
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods.unstake()
+    .accountsPartial({ ...nftAccounts(secondNftMint), seasonEntry: { season: null, userSeason: null } })
+    .rpc();
+
+    await program.methods.setWeightsRoot(new Array(32).fill(0))
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config,
+    })
+    .rpc();
+  })
+
+  // Tier 1 locks for a minute and doubles the stake's weight
+  const lockTiers = [
+    { duration: 0, multiplier: 10_000 },
+    { duration: 60, multiplier: 20_000 },
+    { duration: 0, multiplier: 0 },
+    { duration: 0, multiplier: 0 },
+  ];
+
+  it("Locked stakes can't be unstaked early without a penalty", async() => {
+    await program.methods.setLockTiers(lockTiers, 0)
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config,
+    })
+    .rpc();
+
+    const accounts = nftAccounts(nftMint);
+
+    const tx = await program.methods.stake(10_000, [], 1)
+    .accountsPartial({ ...accounts, collection: collectionMint.publicKey })
+    .rpc();
+    console.log("\nNFT Staked with a lock!");
+    console.log("Your transaction signature", tx);
+
+    const stake = await program.account.stakeAccount.fetch(accounts.stakeAccount);
+    assert.equal(stake.lockMultiplier, 20_000);
+    assert.equal(stake.weight.toNumber(), 20_000);
+    assert.equal(stake.lockUntil.sub(stake.stakedAt).toNumber(), 60);
+
+    // past the freeze period, but still locked
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    let failed = false;
+    try {
+      await program.methods.unstake()
+      .accountsPartial({ ...accounts, seasonEntry: { season: null, userSeason: null } })
+      .rpc();
+    } catch (e) {
+      failed = true;
+      assert.equal((e as anchor.AnchorError).error.errorCode.code, "LockNotExpired");
+    }
+    assert.isTrue(failed);
+  })
+
+  it("Unstaking early burns the penalty share of the stake's points", async() => {
+    await program.methods.setLockTiers(lockTiers, 2_500)
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config,
+    })
+    .rpc();
+
+    const accounts = nftAccounts(nftMint);
+    const stakeBefore = await program.account.stakeAccount.fetch(accounts.stakeAccount);
+
+    // claiming first doesn't pay anything out of the locked stake
+    await program.methods.claim()
+    .accountsPartial({
+      user: provider.wallet.publicKey,
+      userAccount,
+      rewardsMint,
+      config,
+      rewardsAta: getAssociatedTokenAddressSync(rewardsMint, provider.wallet.publicKey),
+      tokenStake: null,
+      seasonEntry: { season: null, userSeason: null },
+      rewardVault: null,
+      systemProgram: SYSTEM_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .remainingAccounts([{ pubkey: accounts.stakeAccount, isSigner: false, isWritable: true }])
+    .rpc();
+
+    const stakeAfterClaim = await program.account.stakeAccount.fetch(accounts.stakeAccount);
+    assert.isTrue(stakeAfterClaim.rewardDebt.eq(stakeBefore.rewardDebt));
+
+    const userBefore = await program.account.userAccount.fetch(userAccount);
+
+    const tx = await program.methods.unstake()
+    .accountsPartial({ ...accounts, seasonEntry: { season: null, userSeason: null } })
+    .rpc();
+    console.log("\nLocked NFT unstaked early!");
+    console.log("Your transaction signature", tx);
+
+    // everything the stake earned since it was staked, less 25%
+    const { accPointsPerWeight } = await program.account.stakeConfig.fetch(config);
+    const earned = stakeBefore.weight.mul(accPointsPerWeight).div(new anchor.BN(10).pow(new anchor.BN(12))).sub(stakeBefore.rewardDebt);
+    const credited = earned.sub(earned.muln(2_500).divn(10_000));
+    assert.isTrue(earned.gtn(0));
+
+    const userAfter = await program.account.userAccount.fetch(userAccount);
+    assert.equal(userAfter.points.sub(userBefore.points).toString(), credited.toString());
+    assert.equal(userAfter.totalPointsEarned.sub(userBefore.totalPointsEarned).toString(), credited.toString());
+    assert.equal(userAfter.amountStaked, 0);
+  })
+
+  // Compressed NFTs live in a Merkle tree without a canopy, so transfers need the full proof.
+  // The tree's leaves are tracked here to build roots and proofs client-side.
+  const merkleTree = generateSigner(umi);
+  const maxDepth = 5;
+  const cnftLeaves: Uint8Array[] = [];
+
+  const cnftMetadata = (collection: PublicKey): MetadataArgsArgs => ({
+    name: "GM",
+    symbol: "GM",
+    uri: "https://arweave.net/123",
+    sellerFeeBasisPoints: 550,
+    primarySaleHappened: false,
+    isMutable: true,
+    editionNonce: none(),
+    tokenStandard: some(TokenStandard.NonFungible),
+    collection: some({ key: collection, verified: true }),
+    uses: none(),
+    tokenProgramVersion: TokenProgramVersion.Original,
+    creators: [],
+  });
+  let memberCnft: MetadataArgsArgs;
+  let otherCnft: MetadataArgsArgs;
+
+  const mintCnft = async (collection: PublicKey) => {
+    const metadata = cnftMetadata(collection);
+    await mintToCollectionV1(umi, {
+      leafOwner: creator.publicKey,
+      merkleTree: merkleTree.publicKey,
+      collectionMint: collection,
+      metadata: { ...metadata, collection: some({ key: collection, verified: false }) },
+    }).sendAndConfirm(umi);
+
+    cnftLeaves.push(hashLeaf(umi, {
+      merkleTree: merkleTree.publicKey,
+      owner: creator.publicKey,
+      leafIndex: cnftLeaves.length,
+      metadata,
+    }));
+    return metadata;
+  };
+
+  const cnftAccounts = (index: number) => {
+    const stakeAccount = anchor.web3.PublicKey.findProgramAddressSync([
+      Buffer.from("stake"),
+      new anchor.web3.PublicKey(findLeafAssetIdPda(umi, { merkleTree: merkleTree.publicKey, leafIndex: index })[0]).toBuffer(),
+      config.toBuffer()
+    ], program.programId)[0];
+
+    return {
+      user: provider.wallet.publicKey,
+      treeConfig: new anchor.web3.PublicKey(findTreeConfigPda(umi, { merkleTree: merkleTree.publicKey })[0]),
+      merkleTree: new anchor.web3.PublicKey(merkleTree.publicKey),
+      config,
+      stakeAccount,
+      userAccount,
+      bubblegumProgram: new anchor.web3.PublicKey(MPL_BUBBLEGUM_PROGRAM_ID),
+      logWrapper: new anchor.web3.PublicKey(SPL_NOOP_PROGRAM_ID),
+      compressionProgram: new anchor.web3.PublicKey(SPL_ACCOUNT_COMPRESSION_PROGRAM_ID),
+      systemProgram: SYSTEM_PROGRAM_ID,
+    };
+  };
+
+  const cnftProof = (index: number) => ({
+    root: Array.from(publicKeyBytes(getMerkleRoot(cnftLeaves, maxDepth))),
+    proof: getMerkleProof(cnftLeaves, maxDepth, cnftLeaves[index], index)
+      .map((node) => ({ pubkey: new anchor.web3.PublicKey(node), isSigner: false, isWritable: false })),
+  });
+
+  it("Create a Merkle tree and mint compressed NFTs", async () => {
+    await (await createTree(umi, { merkleTree, maxDepth, maxBufferSize: 8 })).sendAndConfirm(umi);
+
+    // a second collection whose cNFTs the config must not accept
+    const otherCollection = generateSigner(umi);
+    await createNft(umi, {
+      mint: otherCollection,
+      name: "GM",
+      symbol: "GM",
+      uri: "https://arweave.net/123",
+      sellerFeeBasisPoints: percentAmount(5.5),
+      creators: null,
+      collectionDetails: { __kind: 'V1', size: 0 },
+    }).sendAndConfirm(umi);
+
+    memberCnft = await mintCnft(collectionMint.publicKey);
+    otherCnft = await mintCnft(otherCollection.publicKey);
+
+    // the tree's root matches the leaves built client-side
+    const tree = await fetchMerkleTree(umi, merkleTree.publicKey);
+    assert.equal(getCurrentRoot(tree.tree).toString(), getMerkleRoot(cnftLeaves, maxDepth).toString());
+  })
+
+  it("Stake a cNFT from another collection fails", async() => {
+    const { root, proof } = cnftProof(1);
+
+    let failed = false;
+    try {
+      await program.methods.stakeCnft(root, new anchor.BN(1), 1, Buffer.from(getMetadataArgsSerializer().serialize(otherCnft)), 10_000, [], 0)
+      .accountsPartial({
+        ...cnftAccounts(1),
+        leafDelegate: provider.wallet.publicKey,
+        collection: collectionMint.publicKey,
+      })
+      .remainingAccounts(proof)
+      .rpc();
+    } catch (e) {
+      failed = true;
+      assert.equal((e as anchor.AnchorError).error.errorCode.code, "IncorrectCollection");
+    }
+    assert.isTrue(failed);
+  })
+
+  it("Stake and unstake a cNFT", async() => {
+    const accounts = cnftAccounts(0);
+    let { root, proof } = cnftProof(0);
+
+    let tx = await program.methods.stakeCnft(root, new anchor.BN(0), 0, Buffer.from(getMetadataArgsSerializer().serialize(memberCnft)), 10_000, [], 0)
+    .accountsPartial({
+      ...accounts,
+      leafDelegate: provider.wallet.publicKey,
+      collection: collectionMint.publicKey,
+    })
+    .remainingAccounts(proof)
+    .rpc();
+    console.log("\ncNFT Staked!");
+    console.log("Your transaction signature", tx);
+
+    // the stake account now owns the leaf and is its delegate
+    const stakeOwner = publicKey(accounts.stakeAccount.toBase58());
+    cnftLeaves[0] = hashLeaf(umi, {
+      merkleTree: merkleTree.publicKey,
+      owner: stakeOwner,
+      delegate: stakeOwner,
+      leafIndex: 0,
+      metadata: memberCnft,
+    });
+
+    let tree = await fetchMerkleTree(umi, merkleTree.publicKey);
+    assert.equal(getCurrentRoot(tree.tree).toString(), getMerkleRoot(cnftLeaves, maxDepth).toString());
+
+    let user = await program.account.userAccount.fetch(userAccount);
+    assert.equal(user.amountStaked, 1);
+
+    // let the freeze period run out
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    ({ root, proof } = cnftProof(0));
+    const dataHash = Array.from(hashMetadataData(memberCnft));
+    const creatorHash = Array.from(hashMetadataCreators(memberCnft.creators));
+
+    tx = await program.methods.unstakeCnft(root, dataHash, creatorHash, new anchor.BN(0), 0)
+    .accountsPartial({ ...accounts, seasonEntry: { season: null, userSeason: null } })
+    .remainingAccounts(proof)
+    .rpc();
+    console.log("\ncNFT unstaked!");
+    console.log("Your transaction signature", tx);
+
+    // the leaf is back with the user
+    cnftLeaves[0] = hashLeaf(umi, {
+      merkleTree: merkleTree.publicKey,
+      owner: creator.publicKey,
+      leafIndex: 0,
+      metadata: memberCnft,
+    });
+
+    tree = await fetchMerkleTree(umi, merkleTree.publicKey);
+    assert.equal(getCurrentRoot(tree.tree).toString(), getMerkleRoot(cnftLeaves, maxDepth).toString());
+
+    user = await program.account.userAccount.fetch(userAccount);
+    assert.equal(user.amountStaked, 0);
+    assert.isNull(await provider.connection.getAccountInfo(accounts.stakeAccount));
+  })
+
+  it("Mint and verify programmable NFT", async () => {
+    pnftMint = generateSigner(umi);
+        await createProgrammableNft(umi, {
+            mint: pnftMint,
+            name: "GM",
+            symbol: "GM",
+            uri: "https://arweave.net/123",
+            sellerFeeBasisPoints: percentAmount(5.5),
+            collection: {verified: false, key: collectionMint.publicKey},
+            creators: null,
+        }).sendAndConfirm(umi)
+
+    await verifyCollectionV1(umi, {
+      metadata: findMetadataPda(umi, {mint: pnftMint.publicKey}),
+      collectionMint: collectionMint.publicKey,
+      authority: creator,
+    }).sendAndConfirm(umi)
+    console.log(`\nCreated pNFT: ${pnftMint.publicKey.toString()}`)
+  });
+
+  it("Stake and unstake programmable NFT", async() => {
+    const mint = new anchor.web3.PublicKey(pnftMint.publicKey as PublicKey);
+    const mintAta = getAssociatedTokenAddressSync(mint, provider.wallet.publicKey);
+
+    const accounts = {
+      user: provider.wallet.publicKey,
+      mint,
+      mintAta,
+      metadata: new anchor.web3.PublicKey(findMetadataPda(umi, {mint: pnftMint.publicKey})[0]),
+      edition: new anchor.web3.PublicKey(findMasterEditionPda(umi, {mint: pnftMint.publicKey})[0]),
+      tokenRecord: new anchor.web3.PublicKey(findTokenRecordPda(umi, {mint: pnftMint.publicKey, token: mintAta.toBase58() as PublicKey})[0]),
+      config,
+      stakeAccount: anchor.web3.PublicKey.findProgramAddressSync([
+        Buffer.from("stake"),
+        mint.toBuffer(),
+        config.toBuffer()
+      ], program.programId)[0],
+      userAccount,
+      // no rule set on the test pNFT
+      authorizationRules: null,
+      authorizationRulesProgram: null,
+      sysvarInstructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+    };
+
+    let tx = await program.methods.stakePnft(10_000, [], 0)
+    .accountsPartial({ ...accounts, collection: collectionMint.publicKey })
+    .rpc();
+    console.log("\npNFT Staked!");
+    console.log("Your transaction signature", tx);
+
+    // let the freeze period run out
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    tx = await program.methods.unstakePnft()
+    .accountsPartial({ ...accounts, seasonEntry: { season: null, userSeason: null } })
+    .rpc();
+    console.log("\npNFT unstaked!");
+    console.log("Your transaction signature", tx);
+
+    const account = await program.account.userAccount.fetch(userAccount);
+    assert.equal(account.amountStaked, 0);
+  })
+
+  // A collection NFT owned by the wallet, which is then its update authority
+  const createCollection = async () => {
+    const mint = generateSigner(umi);
+    await createNft(umi, {
+      mint,
+      name: "GM",
+      uri: "https://arweave.net/123",
+      sellerFeeBasisPoints: percentAmount(0),
+      isCollection: true,
+    }).sendAndConfirm(umi);
+    return new anchor.web3.PublicKey(mint.publicKey);
+  };
+
+  it("Fund and initialize a reward vault", async() => {
+    // a second config whose stakers are paid in an existing partner token
+    const collection = await createCollection();
+    const partnerMint = await createMint(provider.connection, payer.payer, provider.publicKey, null, 6);
+
+    const vaultConfig = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("config"), collection.toBuffer()], program.programId)[0];
+    const rewardVault = getAssociatedTokenAddressSync(partnerMint, vaultConfig, true);
+
+    await program.methods.initializeConfig(1, 0, emission)
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      collection,
+      metadata: new anchor.web3.PublicKey(findMetadataPda(umi, {mint: publicKey(collection)})[0]),
+      config: vaultConfig,
+      rewardsMint: anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("rewards"), vaultConfig.toBuffer()], program.programId)[0],
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+    await program.methods.initializeRewardVault()
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config: vaultConfig,
+      rewardsMint: partnerMint,
+      rewardVault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+    const funderAta = await getOrCreateAssociatedTokenAccount(provider.connection, payer.payer, partnerMint, provider.publicKey);
+    await mintTo(provider.connection, payer.payer, partnerMint, funderAta.address, payer.payer, 1_000 * 10 ** 6);
+
+    const tx = await program.methods.fundRewards(new anchor.BN(1_000 * 10 ** 6))
+    .accountsPartial({
+      funder: provider.wallet.publicKey,
+      config: vaultConfig,
+      rewardsMint: partnerMint,
+      funderAta: funderAta.address,
+      rewardVault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+    console.log("\nReward vault funded");
+    console.log("Your transaction signature", tx);
+
+    const account = await program.account.stakeConfig.fetch(vaultConfig);
+    assert.deepEqual(account.rewardMode, { vault: {} });
+    assert.ok(account.rewardsMint.equals(partnerMint));
+    assert.equal(Number((await getAccount(provider.connection, rewardVault)).amount), 1_000 * 10 ** 6);
+  })
+
+  // A config paying rewards out of a partner token vault funded with `funding` whole tokens. The
+  // user stakes one whole token into it, so they earn its entire emission.
+  const setupVaultStaking = async (funding: number) => {
+    const collection = await createCollection();
+    const partnerMint = await createMint(provider.connection, payer.payer, provider.publicKey, null, 6);
+    const stakeMint = await createMint(provider.connection, payer.payer, provider.publicKey, null, 6);
+
+    const vaultConfig = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("config"), collection.toBuffer()], program.programId)[0];
+    const rewardVault = getAssociatedTokenAddressSync(partnerMint, vaultConfig, true);
+    const vaultUserAccount = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("user"), vaultConfig.toBuffer(), provider.publicKey.toBuffer()], program.programId)[0];
+    const tokenVault = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("token_vault"), vaultConfig.toBuffer()], program.programId)[0];
+    const tokenStake = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("token_stake"), vaultConfig.toBuffer(), provider.publicKey.toBuffer()], program.programId)[0];
+
+    await program.methods.initializeConfig(1, 0, emission)
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      collection,
+      metadata: new anchor.web3.PublicKey(findMetadataPda(umi, {mint: publicKey(collection)})[0]),
+      config: vaultConfig,
+      rewardsMint: anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("rewards"), vaultConfig.toBuffer()], program.programId)[0],
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+    await program.methods.initializeRewardVault()
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config: vaultConfig,
+      rewardsMint: partnerMint,
+      rewardVault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+    const funderAta = await getOrCreateAssociatedTokenAccount(provider.connection, payer.payer, partnerMint, provider.publicKey);
+    await mintTo(provider.connection, payer.payer, partnerMint, funderAta.address, payer.payer, funding * 10 ** 6);
+
+    await program.methods.fundRewards(new anchor.BN(funding * 10 ** 6))
+    .accountsPartial({
+      funder: provider.wallet.publicKey,
+      config: vaultConfig,
+      rewardsMint: partnerMint,
+      funderAta: funderAta.address,
+      rewardVault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+    await program.methods.enableTokenStaking(new anchor.BN(10_000))
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config: vaultConfig,
+      stakeMint,
+      tokenVault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+    await program.methods.initializeUser()
+    .accountsPartial({
+      user: provider.wallet.publicKey,
+      config: vaultConfig,
+      userAccount: vaultUserAccount,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .rpc();
+
+    const userAta = await getOrCreateAssociatedTokenAccount(provider.connection, payer.payer, stakeMint, provider.publicKey);
+    await mintTo(provider.connection, payer.payer, stakeMint, userAta.address, payer.payer, 10 ** 6);
+
+    await program.methods.depositTokens(new anchor.BN(10 ** 6))
+    .accountsPartial({
+      user: provider.wallet.publicKey,
+      config: vaultConfig,
+      stakeMint,
+      userAta: userAta.address,
+      tokenVault,
+      tokenStake,
+      userAccount: vaultUserAccount,
+      seasonEntry: { season: null, userSeason: null },
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+    return { vaultConfig, partnerMint, rewardVault, vaultUserAccount, tokenStake };
+  };
+
+  const claimFromVault = (vault: Awaited<ReturnType<typeof setupVaultStaking>>) =>
+    program.methods.claim()
+    .accountsPartial({
+      user: provider.wallet.publicKey,
+      userAccount: vault.vaultUserAccount,
+      rewardsMint: vault.partnerMint,
+      config: vault.vaultConfig,
+      rewardsAta: getAssociatedTokenAddressSync(vault.partnerMint, provider.wallet.publicKey),
+      tokenStake: vault.tokenStake,
+      seasonEntry: { season: null, userSeason: null },
+      rewardVault: vault.rewardVault,
+      systemProgram: SYSTEM_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+  let fundedVault: Awaited<ReturnType<typeof setupVaultStaking>>;
+
+  it("Claim from a reward vault", async() => {
+    fundedVault = await setupVaultStaking(1_000);
+
+    // let some points accrue to the token stake
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const tx = await claimFromVault(fundedVault);
+    console.log("\nRewards claimed from the vault");
+    console.log("Your transaction signature", tx);
+
+    // every point is paid as one whole partner token, transferred out of the vault
+    const user = await program.account.userAccount.fetch(fundedVault.vaultUserAccount);
+    const earned = user.totalPointsEarned.toNumber();
+    assert.isAbove(earned, 0);
+    assert.equal(user.points.toNumber(), 0);
+
+    const rewardsAta = getAssociatedTokenAddressSync(fundedVault.partnerMint, provider.wallet.publicKey);
+    assert.equal(Number((await getAccount(provider.connection, rewardsAta)).amount), earned * 10 ** 6);
+    assert.equal(Number((await getAccount(provider.connection, fundedVault.rewardVault)).amount), (1_000 - earned) * 10 ** 6);
+  })
+
+  it("Initializing a reward vault twice fails", async() => {
+    const otherMint = await createMint(provider.connection, payer.payer, provider.publicKey, null, 6);
+
+    let failed = false;
+    try {
+      await program.methods.initializeRewardVault()
+      .accountsPartial({
+        admin: provider.wallet.publicKey,
+        config: fundedVault.vaultConfig,
+        rewardsMint: otherMint,
+        rewardVault: getAssociatedTokenAddressSync(otherMint, fundedVault.vaultConfig, true),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    } catch (e) {
+      failed = true;
+      assert.equal((e as anchor.AnchorError).error.errorCode.code, "RewardVaultInitialized");
+    }
+    assert.isTrue(failed);
+
+    const account = await program.account.stakeConfig.fetch(fundedVault.vaultConfig);
+    assert.ok(account.rewardsMint.equals(fundedVault.partnerMint));
+  })
+
+  it("Claim pays out what the vault covers, then fails once it's empty", async() => {
+    // 5 tokens cover less than the ~10 points a second the stake earns
+    const vault = await setupVaultStaking(5);
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    await claimFromVault(vault);
+
+    const user = await program.account.userAccount.fetch(vault.vaultUserAccount);
+    assert.isAbove(user.totalPointsEarned.toNumber(), 5);
+    assert.equal(user.points.toNumber(), user.totalPointsEarned.toNumber() - 5);
+
+    const rewardsAta = getAssociatedTokenAddressSync(vault.partnerMint, provider.wallet.publicKey);
+    assert.equal(Number((await getAccount(provider.connection, rewardsAta)).amount), 5 * 10 ** 6);
+    assert.equal(Number((await getAccount(provider.connection, vault.rewardVault)).amount), 0);
+
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    let failed = false;
+    try {
+      await claimFromVault(vault);
+    } catch (e) {
+      failed = true;
+      assert.equal((e as anchor.AnchorError).error.errorCode.code, "RewardsExhausted");
+    }
+    assert.isTrue(failed);
+  })
+
+  it("Deposit and withdraw staked tokens", async() => {
+    const stakeMint = await createMint(provider.connection, payer.payer, provider.publicKey, null, 6);
+    const userAta = await getOrCreateAssociatedTokenAccount(provider.connection, payer.payer, stakeMint, provider.publicKey);
+    await mintTo(provider.connection, payer.payer, stakeMint, userAta.address, payer.payer, 100 * 10 ** 6);
+
+    const tokenVault = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("token_vault"), config.toBuffer()], program.programId)[0];
+    const tokenStake = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("token_stake"), config.toBuffer(), provider.publicKey.toBuffer()], program.programId)[0];
+
+    // one whole token weighs as much as one NFT
+    await program.methods.enableTokenStaking(new anchor.BN(10_000))
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config,
+      stakeMint,
+      tokenVault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+    const accounts = {
+      user: provider.wallet.publicKey,
+      config,
+      stakeMint,
+      userAta: userAta.address,
+      tokenVault,
+      tokenStake,
+      userAccount,
+      seasonEntry: { season: null, userSeason: null },
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    let tx = await program.methods.depositTokens(new anchor.BN(100 * 10 ** 6))
+    .accountsPartial(accounts)
+    .rpc();
+    console.log("\nTokens staked");
+    console.log("Your transaction signature", tx);
+
+    tx = await program.methods.withdrawTokens(new anchor.BN(40 * 10 ** 6))
+    .accountsPartial(accounts)
+    .rpc();
+    console.log("\nTokens withdrawn");
+    console.log("Your transaction signature", tx);
+
+    const stake = await program.account.tokenStakeAccount.fetch(tokenStake);
+    assert.equal(stake.amount.toNumber(), 60 * 10 ** 6);
+    assert.equal(stake.weight.toNumber(), 60 * 10_000);
+    assert.equal(Number((await getAccount(provider.connection, tokenVault)).amount), 60 * 10 ** 6);
+  })
+
+  it("Emergency unstake while paused", async() => {
+    const mint = new anchor.web3.PublicKey(secondNftMint.publicKey as PublicKey);
+
+    const accounts = {
+      user: provider.wallet.publicKey,
+      mint,
+      mintAta: getAssociatedTokenAddressSync(mint, provider.wallet.publicKey),
+      metadata: new anchor.web3.PublicKey(findMetadataPda(umi, {mint: secondNftMint.publicKey})[0]),
+      edition: new anchor.web3.PublicKey(findMasterEditionPda(umi, {mint: secondNftMint.publicKey})[0]),
+      config,
+      stakeAccount: anchor.web3.PublicKey.findProgramAddressSync([
+        Buffer.from("stake"),
+        mint.toBuffer(),
+        config.toBuffer()
+      ], program.programId)[0],
+      userAccount,
+    };
+
+    await program.methods.stake(10_000, [], 0)
+    .accountsPartial({ ...accounts, collection: collectionMint.publicKey })
+    .rpc();
+
+    await program.methods.pause()
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config,
+    })
+    .rpc();
+
+    // the freeze period hasn't passed, but the NFT can still be recovered
+    const tx = await program.methods.emergencyUnstake()
+    .accountsPartial({ ...accounts, seasonEntry: { season: null, userSeason: null } })
+    .rpc();
+    console.log("\nNFT recovered with an emergency unstake");
+    console.log("Your transaction signature", tx);
+
+    await program.methods.unpause()
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config,
+    })
+    .rpc();
+
+    const account = await program.account.userAccount.fetch(userAccount);
+    assert.equal(account.amountStaked, 0);
+    assert.isFalse((await program.account.stakeConfig.fetch(config)).paused);
+  })
+
+  it("Season leaderboard", async() => {
+    const id = 1;
+    const season = anchor.web3.PublicKey.findProgramAddressSync([
+      Buffer.from("season"),
+      config.toBuffer(),
+      new anchor.BN(id).toArrayLike(Buffer, "le", 4)
+    ], program.programId)[0];
+    const userSeason = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("user_season"), season.toBuffer(), provider.publicKey.toBuffer()], program.programId)[0];
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods.createSeason(id, new anchor.BN(now - 5), new anchor.BN(now + 5))
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config,
+      season,
+    })
+    .rpc();
+
+    await program.methods.joinSeason()
+    .accountsPartial({
+      user: provider.wallet.publicKey,
+      config,
+      season,
+      userAccount,
+      userSeason,
+    })
+    .rpc();
+
+    // the token stake from above keeps earning points
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const earnedBefore = (await program.account.userAccount.fetch(userAccount)).totalPointsEarned;
+
+    await program.methods.claim()
+    .accountsPartial({
+      user: provider.wallet.publicKey,
+      userAccount,
+      rewardsMint,
+      config,
+      rewardsAta: getAssociatedTokenAddressSync(rewardsMint, provider.wallet.publicKey),
+      tokenStake: anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("token_stake"), config.toBuffer(), provider.publicKey.toBuffer()], program.programId)[0],
+      seasonEntry: { season, userSeason },
+      rewardVault: null,
+      systemProgram: SYSTEM_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+    // only the share the token stake earned after joining counts toward the season
+    const claimed = (await program.account.userAccount.fetch(userAccount)).totalPointsEarned.sub(earnedBefore);
+    const seasonPoints = (await program.account.userSeason.fetch(userSeason)).points;
+    assert.isTrue(seasonPoints.gtn(0));
+    assert.isTrue(seasonPoints.lt(claimed));
+
+    let account = await program.account.season.fetch(season);
+    assert.ok(account.leaderboard[0].user.equals(provider.publicKey));
+    assert.equal(account.leaderboard[0].points.toNumber(), seasonPoints.toNumber());
+
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+
+    const tx = await program.methods.finalizeSeason()
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      config,
+      season,
+    })
+    .rpc();
+    console.log("\nSeason finalized");
+    console.log("Your transaction signature", tx);
+
+    account = await program.account.season.fetch(season);
+    assert.isTrue(account.finalized);
   })
 });
@@ -0,0 +1,36 @@
+use anchor_lang::{prelude::Pubkey, Discriminator, Space};
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+
+use crate::Escrow;
+
+// `getProgramAccounts` filters matching every live escrow of the program.
+pub fn escrow_filters() -> Vec<RpcFilterType> {
+    vec![
+        RpcFilterType::DataSize((8 + Escrow::INIT_SPACE) as u64),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &Escrow::DISCRIMINATOR)),
+    ]
+}
+
+// Escrows created by `maker`.
+pub fn escrow_filters_by_maker(maker: &Pubkey) -> Vec<RpcFilterType> {
+    let mut filters = escrow_filters();
+    filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+        Escrow::MAKER_OFFSET,
+        maker.as_ref(),
+    )));
+    filters
+}
+
+// Escrows offering `mint_a` in exchange for `mint_b`.
+pub fn escrow_filters_by_mint_pair(mint_a: &Pubkey, mint_b: &Pubkey) -> Vec<RpcFilterType> {
+    let mut filters = escrow_filters();
+    filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+        Escrow::MINT_A_OFFSET,
+        mint_a.as_ref(),
+    )));
+    filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+        Escrow::MINT_B_OFFSET,
+        mint_b.as_ref(),
+    )));
+    filters
+}
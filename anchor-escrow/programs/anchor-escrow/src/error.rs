@@ -4,4 +4,26 @@ use anchor_lang::prelude::*;
 pub enum ErrorCode {
     #[msg("Custom error message")]
     CustomError,
+    #[msg("Fee cannot exceed 10000 basis points")]
+    InvalidFee,
+    #[msg("Overflow")]
+    Overflow,
+    #[msg("Incorrect collection")]
+    IncorrectCollection,
+    #[msg("Collection not verified")]
+    CollectionNotVerified,
+    #[msg("Escrow is a collection offer")]
+    CollectionOffer,
+    #[msg("Escrow is not a collection offer")]
+    NotCollectionOffer,
+    #[msg("Escrow has expired")]
+    Expired,
+    #[msg("Escrow hasn't expired yet")]
+    NotExpired,
+    #[msg("Expiry must be 0 or in the future")]
+    InvalidExpiry,
+    #[msg("Only the maker can refund an escrow")]
+    NotMaker,
+    #[msg("Only the program's upgrade authority can initialize the config")]
+    NotUpgradeAuthority,
 }
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EscrowMade {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub deposit_amount: u64,
+    pub recieve_amount: u64,
+    pub fee_bps: u16,
+    pub collection: Option<Pubkey>,
+}
+
+#[event]
+pub struct EscrowTaken {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct EscrowRefunded {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub amount_a: u64,
+}
+
+#[event]
+pub struct EscrowExpired {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub amount_a: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct EscrowUpdated {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub recieve_amount: u64,
+    pub expires_at: i64,
+}
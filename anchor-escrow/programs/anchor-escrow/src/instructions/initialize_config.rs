@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, program::AnchorEscrow, EscrowConfig, BPS_DENOMINATOR};
+
+// The config is a singleton, so only the program's upgrade authority may create it.
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, AnchorEscrow>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + EscrowConfig::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    pub system_program: Program<'info, System>,
+}
+impl<'info> InitializeConfig<'info> {
+    pub fn initialize_config(
+        &mut self,
+        fee_bps: u16,
+        treasury: Pubkey,
+        bumps: InitializeConfigBumps,
+    ) -> Result<()> {
+        require!(fee_bps <= BPS_DENOMINATOR, ErrorCode::InvalidFee);
+
+        self.config.set_inner(EscrowConfig {
+            admin: self.admin.key(),
+            fee_bps,
+            treasury,
+            bump: bumps.config,
+        });
+        Ok(())
+    }
+}
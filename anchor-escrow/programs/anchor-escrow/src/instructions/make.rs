@@ -1,10 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
-use crate::Escrow;
+use crate::{utils::transfer_checked_with_hook, Escrow, EscrowConfig, EscrowMade};
 
 #[derive(Accounts)]
 #[instruction(seed:u64)]
@@ -12,9 +12,13 @@ pub struct Make<'info> {
     #[account(mut)]
     pub maker: Signer<'info>,
     pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = token_program_b)]
     pub mint_b: InterfaceAccount<'info, Mint>,
 
-    #[account(mut, associated_token::mint = mint_a, associated_token::authority = maker)]
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, EscrowConfig>,
+
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = maker, associated_token::token_program = token_program_a)]
     pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
@@ -29,11 +33,12 @@ pub struct Make<'info> {
     )]
     pub escrow: Account<'info, Escrow>,
 
-    #[account(init,payer =maker,associated_token::authority = escrow,associated_token::mint=mint_a)]
+    #[account(init,payer =maker,associated_token::authority = escrow,associated_token::mint=mint_a,associated_token::token_program = token_program_a)]
     pub vault: InterfaceAccount<'info, TokenAccount>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
 }
 impl<'info> Make<'info> {
     pub fn initialize_escrow(
@@ -41,6 +46,7 @@ impl<'info> Make<'info> {
         seed: u64,
         bumps: MakeBumps,
         recieve_amount: u64,
+        collection_offer: bool,
     ) -> Result<()> {
         self.escrow.set_inner(Escrow {
             seed,
@@ -49,20 +55,40 @@ impl<'info> Make<'info> {
             mint_b: self.mint_b.key(),
             bump: bumps.escrow,
             recieve_amount,
+            fee_bps: self.config.fee_bps,
+            collection_offer,
+            expires_at: 0,
+            collection: None,
         });
         Ok(())
     }
-    pub fn deposit_into_escrow(&mut self) -> Result<()> {
+    pub fn deposit_into_escrow(
+        &mut self,
+        amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
         let accounts = TransferChecked {
             from: self.maker_ata_a.to_account_info(),
             mint: self.mint_a.to_account_info(),
             to: self.vault.to_account_info(),
-            authority: self.escrow.to_account_info(),
+            authority: self.maker.to_account_info(),
         };
 
-        let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+        let ctx = CpiContext::new(self.token_program_a.to_account_info(), accounts)
+            .with_remaining_accounts(remaining_accounts.to_vec());
+
+        transfer_checked_with_hook(ctx, amount, self.mint_a.decimals)?;
 
-        transfer_checked(ctx, self.escrow.recieve_amount, self.mint_a.decimals)?;
+        emit!(EscrowMade {
+            seed: self.escrow.seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            deposit_amount: amount,
+            recieve_amount: self.escrow.recieve_amount,
+            fee_bps: self.escrow.fee_bps,
+            collection: None,
+        });
         Ok(())
     }
 }
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{MasterEditionAccount, Metadata, MetadataAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    error::ErrorCode, utils::transfer_checked_with_hook, Escrow, EscrowConfig, EscrowMade,
+};
+
+#[derive(Accounts)]
+#[instruction(seed:u64)]
+pub struct MakeNft<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub collection: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, EscrowConfig>,
+
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint_a.key().as_ref()
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+        constraint = metadata.collection.as_ref().map(|c| c.key) == Some(collection.key()) @ ErrorCode::IncorrectCollection,
+        constraint = metadata.collection.as_ref().is_some_and(|c| c.verified) @ ErrorCode::CollectionNotVerified,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint_a.key().as_ref(),
+            b"edition"
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub edition: Account<'info, MasterEditionAccount>,
+
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = maker, associated_token::token_program = token_program)]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,payer = maker,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [
+            b"escrow",
+            maker.key().as_ref(),
+            seed.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(init,payer =maker,associated_token::authority = escrow,associated_token::mint=mint_a,associated_token::token_program = token_program)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+impl<'info> MakeNft<'info> {
+    pub fn initialize_escrow(
+        &mut self,
+        seed: u64,
+        bumps: MakeNftBumps,
+        recieve_amount: u64,
+    ) -> Result<()> {
+        self.escrow.set_inner(Escrow {
+            seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            bump: bumps.escrow,
+            recieve_amount,
+            fee_bps: self.config.fee_bps,
+            collection_offer: false,
+            expires_at: 0,
+            collection: Some(self.collection.key()),
+        });
+        Ok(())
+    }
+    pub fn deposit_nft(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.token_program.to_account_info(), accounts)
+            .with_remaining_accounts(remaining_accounts.to_vec());
+
+        transfer_checked_with_hook(ctx, 1, self.mint_a.decimals)?;
+
+        emit!(EscrowMade {
+            seed: self.escrow.seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            deposit_amount: 1,
+            recieve_amount: self.escrow.recieve_amount,
+            fee_bps: self.escrow.fee_bps,
+            collection: self.escrow.collection,
+        });
+        Ok(())
+    }
+}
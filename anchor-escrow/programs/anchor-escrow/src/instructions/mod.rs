@@ -1,5 +1,23 @@
+pub mod initialize_config;
+pub use initialize_config::*;
+
+pub mod update_config;
+pub use update_config::*;
+
 pub mod make;
 pub use make::*;
 
+pub mod make_nft;
+pub use make_nft::*;
+
 pub mod take;
 pub use take::*;
+
+pub mod take_nft;
+pub use take_nft::*;
+
+pub mod update_escrow;
+pub use update_escrow::*;
+
+pub mod refund;
+pub use refund::*;
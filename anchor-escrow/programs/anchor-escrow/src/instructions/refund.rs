@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{
+    error::ErrorCode, utils::transfer_checked_with_hook, Escrow, EscrowExpired, EscrowRefunded,
+};
+
+// Returns the deposit to the maker and closes the escrow. Used by `refund`, signed by the
+// maker at any time, and by `expire`, which anyone can crank once the offer has expired.
+#[derive(Accounts)]
+#[instruction(seed:u64)]
+pub struct Refund<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(init_if_needed,payer = signer, associated_token::mint = mint_a, associated_token::authority = maker, associated_token::token_program = token_program_a)]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        seeds = [
+            b"escrow",
+            escrow.maker.as_ref(),
+            seed.to_le_bytes().as_ref()
+        ],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = escrow, associated_token::token_program = token_program_a)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+}
+impl<'info> Refund<'info> {
+    pub fn refund(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        require_keys_eq!(self.signer.key(), self.maker.key(), ErrorCode::NotMaker);
+
+        let amount_a = self.return_to_maker(remaining_accounts)?;
+
+        emit!(EscrowRefunded {
+            seed: self.escrow.seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.escrow.mint_b,
+            amount_a,
+        });
+        Ok(())
+    }
+    pub fn expire(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        require!(
+            self.escrow.is_expired(Clock::get()?.unix_timestamp),
+            ErrorCode::NotExpired
+        );
+
+        let amount_a = self.return_to_maker(remaining_accounts)?;
+
+        emit!(EscrowExpired {
+            seed: self.escrow.seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.escrow.mint_b,
+            amount_a,
+            expires_at: self.escrow.expires_at,
+        });
+        Ok(())
+    }
+    fn return_to_maker(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<u64> {
+        let escrow_seed = self.escrow.seed.to_le_bytes();
+        let seeds = [
+            b"escrow",
+            self.escrow.maker.as_ref(),
+            escrow_seed.as_ref(),
+            &[self.escrow.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[&seeds[..]];
+
+        let amount_a = self.vault.amount;
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.maker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program_a.to_account_info(),
+            accounts,
+            signer_seeds,
+        )
+        .with_remaining_accounts(remaining_accounts.to_vec());
+
+        transfer_checked_with_hook(ctx, amount_a, self.mint_a.decimals)?;
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program_a.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+
+        close_account(ctx)?;
+
+        Ok(amount_a)
+    }
+}
@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{
-    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
 };
 
-use crate::Escrow;
+use crate::{
+    error::ErrorCode, utils::transfer_checked_with_hook, Escrow, EscrowConfig, EscrowTaken,
+};
 
 #[derive(Accounts)]
 #[instruction(seed:u64)]
@@ -17,18 +19,29 @@ pub struct Take<'info> {
     pub mint_a: InterfaceAccount<'info, Mint>,
     pub mint_b: InterfaceAccount<'info, Mint>,
 
-    #[account(mut, associated_token::mint = mint_a, associated_token::authority = maker)]
-    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
-    #[account(init_if_needed,payer = taker, associated_token::mint = mint_b, associated_token::authority = maker)]
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, EscrowConfig>,
+    /// CHECK: only used as the authority of the treasury ATA, checked against the config
+    #[account(address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(init_if_needed,payer = taker, associated_token::mint = mint_b, associated_token::authority = maker, associated_token::token_program = token_program_b)]
     pub maker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    #[account(init_if_needed,payer = taker, associated_token::mint = mint_b, associated_token::authority = treasury, associated_token::token_program = token_program_b)]
+    pub treasury_ata_b: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(init_if_needed,payer = taker, associated_token::mint = mint_a, associated_token::authority = taker)]
+    #[account(init_if_needed,payer = taker, associated_token::mint = mint_a, associated_token::authority = taker, associated_token::token_program = token_program_a)]
     pub taker_ata_a: InterfaceAccount<'info, TokenAccount>,
-    #[account(mut, associated_token::mint = mint_b, associated_token::authority = taker)]
+    #[account(mut, associated_token::mint = mint_b, associated_token::authority = taker, associated_token::token_program = token_program_b)]
     pub taker_ata_b: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
-mut,
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = !escrow.collection_offer @ ErrorCode::CollectionOffer,
         seeds = [
             b"escrow",
             escrow.maker.as_ref(),
@@ -38,13 +51,22 @@ mut,
     )]
     pub escrow: Account<'info, Escrow>,
 
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = escrow, associated_token::token_program = token_program_a)]
     pub vault: InterfaceAccount<'info, TokenAccount>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
 }
 impl<'info> Take<'info> {
-    pub fn deposit_into_vault(&mut self) -> Result<()> {
+    pub fn deposit_into_vault(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        require!(
+            !self.escrow.is_expired(Clock::get()?.unix_timestamp),
+            ErrorCode::Expired
+        );
+
+        let fee = self.escrow.fee(self.escrow.recieve_amount)?;
+
         let accounts = TransferChecked {
             from: self.taker_ata_b.to_account_info(),
             mint: self.mint_b.to_account_info(),
@@ -52,17 +74,30 @@ impl<'info> Take<'info> {
             authority: self.taker.to_account_info(),
         };
 
-        let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+        let ctx = CpiContext::new(self.token_program_b.to_account_info(), accounts)
+            .with_remaining_accounts(remaining_accounts.to_vec());
 
-        transfer_checked(ctx, self.escrow.recieve_amount, self.mint_a.decimals)?;
+        transfer_checked_with_hook(ctx, self.escrow.recieve_amount - fee, self.mint_b.decimals)?;
 
-        let accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            mint: self.mint_a.to_account_info(),
-            to: self.taker_ata_a.to_account_info(),
-            authority: self.escrow.to_account_info(),
-        };
+        if fee > 0 {
+            let accounts = TransferChecked {
+                from: self.taker_ata_b.to_account_info(),
+                mint: self.mint_b.to_account_info(),
+                to: self.treasury_ata_b.to_account_info(),
+                authority: self.taker.to_account_info(),
+            };
 
+            let ctx = CpiContext::new(self.token_program_b.to_account_info(), accounts)
+                .with_remaining_accounts(remaining_accounts.to_vec());
+
+            transfer_checked_with_hook(ctx, fee, self.mint_b.decimals)?;
+        }
+        Ok(())
+    }
+    pub fn withdraw_and_close_vault(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
         let escrow_seed = self.escrow.seed.to_le_bytes();
         let seeds = [
             b"escrow",
@@ -72,26 +107,48 @@ impl<'info> Take<'info> {
         ];
         let signer_seeds: &[&[&[u8]]] = &[&seeds[..]];
 
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.taker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
         let ctx = CpiContext::new_with_signer(
-            self.system_program.to_account_info(),
+            self.token_program_a.to_account_info(),
             accounts,
             signer_seeds,
-        );
+        )
+        .with_remaining_accounts(remaining_accounts.to_vec());
 
-        transfer_checked(ctx, self.escrow.recieve_amount, self.mint_a.decimals)?;
-        Ok(())
-    }
-    pub fn withdraw_and_close_vault(&mut self) -> Result<()> {
-        let accounts = TransferChecked {
-            from: self.maker_ata_a.to_account_info(),
-            mint: self.mint_a.to_account_info(),
-            to: self.vault.to_account_info(),
+        let amount_a = self.vault.amount;
+
+        transfer_checked_with_hook(ctx, amount_a, self.mint_a.decimals)?;
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
             authority: self.escrow.to_account_info(),
         };
 
-        let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+        let ctx = CpiContext::new_with_signer(
+            self.token_program_a.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+
+        close_account(ctx)?;
 
-        transfer_checked(ctx, self.vault.amount, self.mint_a.decimals)?;
+        emit!(EscrowTaken {
+            seed: self.escrow.seed,
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            amount_a,
+            amount_b: self.escrow.recieve_amount,
+            fee: self.escrow.fee(self.escrow.recieve_amount)?,
+        });
         Ok(())
     }
 }
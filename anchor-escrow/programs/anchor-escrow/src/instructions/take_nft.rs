@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::{MasterEditionAccount, Metadata, MetadataAccount};
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{
+    error::ErrorCode, utils::transfer_checked_with_hook, Escrow, EscrowConfig, EscrowTaken,
+};
+
+// Fills a collection offer: the taker hands over any verified NFT of the collection
+// stored in `escrow.mint_b` and receives the maker's deposit, minus the fee.
+#[derive(Accounts)]
+#[instruction(seed:u64)]
+pub struct TakeNft<'info> {
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    #[account(address = escrow.mint_b)]
+    pub collection: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+        constraint = metadata.collection.as_ref().map(|c| c.key) == Some(collection.key()) @ ErrorCode::IncorrectCollection,
+        constraint = metadata.collection.as_ref().is_some_and(|c| c.verified) @ ErrorCode::CollectionNotVerified,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            nft_mint.key().as_ref(),
+            b"edition"
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub edition: Account<'info, MasterEditionAccount>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, EscrowConfig>,
+    /// CHECK: only used as the authority of the treasury ATA, checked against the config
+    #[account(address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(init_if_needed,payer = taker, associated_token::mint = nft_mint, associated_token::authority = maker, associated_token::token_program = token_program)]
+    pub maker_nft_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, associated_token::mint = nft_mint, associated_token::authority = taker, associated_token::token_program = token_program)]
+    pub taker_nft_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(init_if_needed,payer = taker, associated_token::mint = mint_a, associated_token::authority = taker, associated_token::token_program = token_program)]
+    pub taker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(init_if_needed,payer = taker, associated_token::mint = mint_a, associated_token::authority = treasury, associated_token::token_program = token_program)]
+    pub treasury_ata_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        constraint = escrow.collection_offer @ ErrorCode::NotCollectionOffer,
+        seeds = [
+            b"escrow",
+            escrow.maker.as_ref(),
+            seed.to_le_bytes().as_ref()
+        ],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut, associated_token::mint = mint_a, associated_token::authority = escrow, associated_token::token_program = token_program)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub metadata_program: Program<'info, Metadata>,
+}
+impl<'info> TakeNft<'info> {
+    pub fn send_nft(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        require!(
+            !self.escrow.is_expired(Clock::get()?.unix_timestamp),
+            ErrorCode::Expired
+        );
+
+        let accounts = TransferChecked {
+            from: self.taker_nft_ata.to_account_info(),
+            mint: self.nft_mint.to_account_info(),
+            to: self.maker_nft_ata.to_account_info(),
+            authority: self.taker.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.token_program.to_account_info(), accounts)
+            .with_remaining_accounts(remaining_accounts.to_vec());
+
+        transfer_checked_with_hook(ctx, 1, self.nft_mint.decimals)?;
+        Ok(())
+    }
+    pub fn withdraw_and_close_vault(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let escrow_seed = self.escrow.seed.to_le_bytes();
+        let seeds = [
+            b"escrow",
+            self.escrow.maker.as_ref(),
+            escrow_seed.as_ref(),
+            &[self.escrow.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[&seeds[..]];
+
+        let amount_a = self.vault.amount;
+        let fee = self.escrow.fee(amount_a)?;
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.taker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        )
+        .with_remaining_accounts(remaining_accounts.to_vec());
+
+        transfer_checked_with_hook(ctx, amount_a - fee, self.mint_a.decimals)?;
+
+        if fee > 0 {
+            let accounts = TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                to: self.treasury_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            )
+            .with_remaining_accounts(remaining_accounts.to_vec());
+
+            transfer_checked_with_hook(ctx, fee, self.mint_a.decimals)?;
+        }
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+
+        close_account(ctx)?;
+
+        emit!(EscrowTaken {
+            seed: self.escrow.seed,
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.nft_mint.key(),
+            amount_a,
+            amount_b: 1,
+            fee,
+        });
+        Ok(())
+    }
+}
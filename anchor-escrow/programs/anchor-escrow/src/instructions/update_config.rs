@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, EscrowConfig, BPS_DENOMINATOR};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, EscrowConfig>,
+}
+impl<'info> UpdateConfig<'info> {
+    pub fn update_config(&mut self, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+        require!(fee_bps <= BPS_DENOMINATOR, ErrorCode::InvalidFee);
+
+        self.config.fee_bps = fee_bps;
+        self.config.treasury = treasury;
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, Escrow, EscrowUpdated};
+
+#[derive(Accounts)]
+#[instruction(seed:u64)]
+pub struct UpdateEscrow<'info> {
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = maker,
+        seeds = [
+            b"escrow",
+            maker.key().as_ref(),
+            seed.to_le_bytes().as_ref()
+        ],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+impl<'info> UpdateEscrow<'info> {
+    // Only the expiry can change. The price is fixed at `make` so a pending `take` can't be
+    // front-run into paying more.
+    pub fn update_escrow(&mut self, expires_at: i64) -> Result<()> {
+        require!(
+            expires_at == 0 || expires_at > Clock::get()?.unix_timestamp,
+            ErrorCode::InvalidExpiry
+        );
+
+        self.escrow.expires_at = expires_at;
+
+        emit!(EscrowUpdated {
+            seed: self.escrow.seed,
+            maker: self.maker.key(),
+            mint_a: self.escrow.mint_a,
+            mint_b: self.escrow.mint_b,
+            recieve_amount: self.escrow.recieve_amount,
+            expires_at,
+        });
+        Ok(())
+    }
+}
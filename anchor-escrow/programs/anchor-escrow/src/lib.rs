@@ -1,11 +1,16 @@
+#[cfg(feature = "client")]
+pub mod client;
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 
 pub use constants::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -15,12 +20,86 @@ declare_id!("3ZSratuRHNTmgE9YHA6HanPGkBU1wfDT1ZgwqfsyC1yy");
 pub mod anchor_escrow {
     use super::*;
 
-    pub fn make(ctx: Context<Make>, seed: u64, recieve_amount: u64) -> Result<()> {
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        fee_bps: u16,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.initialize_config(fee_bps, treasury, ctx.bumps)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, fee_bps: u16, treasury: Pubkey) -> Result<()> {
+        ctx.accounts.update_config(fee_bps, treasury)
+    }
+
+    pub fn make<'info>(
+        ctx: Context<'_, '_, '_, 'info, Make<'info>>,
+        seed: u64,
+        recieve_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .initialize_escrow(seed, ctx.bumps, recieve_amount, false)?;
+
+        ctx.accounts
+            .deposit_into_escrow(recieve_amount, ctx.remaining_accounts)?;
+
+        Ok(())
+    }
+
+    pub fn make_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, MakeNft<'info>>,
+        seed: u64,
+        recieve_amount: u64,
+    ) -> Result<()> {
         ctx.accounts
             .initialize_escrow(seed, ctx.bumps, recieve_amount)?;
 
-        ctx.accounts.deposit_into_escrow()?;
+        ctx.accounts.deposit_nft(ctx.remaining_accounts)?;
+
+        Ok(())
+    }
+
+    pub fn make_collection_offer<'info>(
+        ctx: Context<'_, '_, '_, 'info, Make<'info>>,
+        seed: u64,
+        deposit_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.initialize_escrow(seed, ctx.bumps, 1, true)?;
+
+        ctx.accounts
+            .deposit_into_escrow(deposit_amount, ctx.remaining_accounts)?;
 
         Ok(())
     }
+
+    pub fn take<'info>(ctx: Context<'_, '_, '_, 'info, Take<'info>>, _seed: u64) -> Result<()> {
+        ctx.accounts.deposit_into_vault(ctx.remaining_accounts)?;
+
+        ctx.accounts.withdraw_and_close_vault(ctx.remaining_accounts)?;
+
+        Ok(())
+    }
+
+    pub fn take_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, TakeNft<'info>>,
+        _seed: u64,
+    ) -> Result<()> {
+        ctx.accounts.send_nft(ctx.remaining_accounts)?;
+
+        ctx.accounts.withdraw_and_close_vault(ctx.remaining_accounts)?;
+
+        Ok(())
+    }
+
+    pub fn update_escrow(ctx: Context<UpdateEscrow>, _seed: u64, expires_at: i64) -> Result<()> {
+        ctx.accounts.update_escrow(expires_at)
+    }
+
+    pub fn refund<'info>(ctx: Context<'_, '_, '_, 'info, Refund<'info>>, _seed: u64) -> Result<()> {
+        ctx.accounts.refund(ctx.remaining_accounts)
+    }
+
+    pub fn expire<'info>(ctx: Context<'_, '_, '_, 'info, Refund<'info>>, _seed: u64) -> Result<()> {
+        ctx.accounts.expire(ctx.remaining_accounts)
+    }
 }
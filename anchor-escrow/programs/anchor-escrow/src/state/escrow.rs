@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::{error::ErrorCode, BPS_DENOMINATOR};
+
+// Field order is part of the client API: `maker`, `mint_a` and `mint_b` sit at fixed
+// offsets so escrows can be found with memcmp filters. Add new fields at the end.
 #[account]
 #[derive(InitSpace)]
 pub struct Escrow {
@@ -8,5 +12,62 @@ pub struct Escrow {
     pub mint_a: Pubkey,
     pub mint_b: Pubkey,
     pub recieve_amount: u64,
+    pub fee_bps: u16,
     pub bump: u8,
+    // When set, `mint_b` is a collection mint and any verified NFT of it fills the offer.
+    pub collection_offer: bool,
+    // Unix timestamp after which the offer can't be taken and anyone may return the deposit
+    // to the maker. 0 means the offer never expires.
+    pub expires_at: i64,
+    // Verified collection of the NFT deposited by `make_nft`, None for fungible deposits.
+    pub collection: Option<Pubkey>,
+}
+
+impl Escrow {
+    pub const MAKER_OFFSET: usize = 8 + 8;
+    pub const MINT_A_OFFSET: usize = Self::MAKER_OFFSET + 32;
+    pub const MINT_B_OFFSET: usize = Self::MINT_A_OFFSET + 32;
+
+    // Fee taken out of the fungible leg of a trade, using the rate snapshotted at `make`.
+    pub fn fee(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.fee_bps as u128)
+            .ok_or(ErrorCode::Overflow)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(ErrorCode::Overflow)?;
+
+        Ok(fee as u64)
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_match_the_serialized_layout() {
+        let escrow = Escrow {
+            seed: 7,
+            maker: Pubkey::new_unique(),
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            recieve_amount: 1_000,
+            fee_bps: 250,
+            bump: 255,
+            collection_offer: false,
+            expires_at: 0,
+            collection: None,
+        };
+        let mut data = Vec::new();
+        escrow.try_serialize(&mut data).unwrap();
+
+        let at = |offset: usize| &data[offset..offset + 32];
+        assert_eq!(at(Escrow::MAKER_OFFSET), escrow.maker.as_ref());
+        assert_eq!(at(Escrow::MINT_A_OFFSET), escrow.mint_a.as_ref());
+        assert_eq!(at(Escrow::MINT_B_OFFSET), escrow.mint_b.as_ref());
+    }
 }
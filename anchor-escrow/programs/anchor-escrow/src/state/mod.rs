@@ -1,2 +1,5 @@
+pub mod config;
+pub use config::*;
+
 pub mod escrow;
 pub use escrow::*;
@@ -1,16 +1,649 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
 import { AnchorEscrow } from "../target/types/anchor_escrow";
+import { TransferHook } from "../target/types/transfer_hook";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializeTransferHookInstruction,
+  createMint,
+  getMintLen,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { createNft, findMasterEditionPda, findMetadataPda, mplTokenMetadata, verifySizedCollectionItem } from "@metaplex-foundation/mpl-token-metadata";
+import { createUmi } from "@metaplex-foundation/umi-bundle-defaults";
+import { KeypairSigner, createSignerFromKeypair, generateSigner, keypairIdentity, percentAmount, publicKey } from "@metaplex-foundation/umi";
+import { assert } from "chai";
 
 describe("anchor-escrow", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.AnchorEscrow as Program<AnchorEscrow>;
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
+  const maker = (provider.wallet as anchor.Wallet).payer;
+  const taker = anchor.web3.Keypair.generate();
+  const treasury = anchor.web3.Keypair.generate();
+
+  const seed = new BN(1);
+  const amount = new BN(1_000_000);
+  const feeBps = 250;
+
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+
+  const config = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId)[0];
+  const escrowAddress = (seed: BN) => anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("escrow"), maker.publicKey.toBuffer(), seed.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+  const escrow = escrowAddress(seed);
+
+  // Names of the program events logged by a confirmed transaction
+  const eventNames = async (signature: string) => {
+    await provider.connection.confirmTransaction(signature, "confirmed");
+    const tx = await provider.connection.getTransaction(signature, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    return Array.from(parser.parseLogs(tx.meta.logMessages)).map((event) => event.name);
+  };
+
+  it("Setup mints", async () => {
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(taker.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL)
+    );
+
+    mintA = await createMint(provider.connection, maker, maker.publicKey, null, 6);
+    mintB = await createMint(provider.connection, maker, maker.publicKey, null, 6);
+
+    const makerAtaA = await getOrCreateAssociatedTokenAccount(provider.connection, maker, mintA, maker.publicKey);
+    const takerAtaB = await getOrCreateAssociatedTokenAccount(provider.connection, maker, mintB, taker.publicKey);
+
+    await mintTo(provider.connection, maker, mintA, makerAtaA.address, maker, amount.toNumber());
+    await mintTo(provider.connection, maker, mintB, takerAtaB.address, maker, amount.toNumber());
+  });
+
+  it("Initialize config", async () => {
+    const programData = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      anchor.web3.BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+    )[0];
+    const initializeConfig = (admin: anchor.web3.Keypair) => program.methods.initializeConfig(feeBps, treasury.publicKey)
+    .accountsPartial({
+      admin: admin.publicKey,
+      program: program.programId,
+      programData,
+      config,
+    })
+    .signers([admin]);
+
+    // The taker isn't the upgrade authority, so it can't claim the config first
+    try {
+      await initializeConfig(taker).rpc();
+      assert.fail("only the upgrade authority should initialize the config");
+    } catch (err) {
+      assert.include(err.toString(), "NotUpgradeAuthority");
+    }
+
+    const tx = await initializeConfig(maker).rpc();
     console.log("Your transaction signature", tx);
   });
+
+  it("Make", async () => {
+    const tx = await program.methods.make(seed, amount)
+    .accountsPartial({
+      maker: maker.publicKey,
+      mintA,
+      mintB,
+      config,
+      makerAtaA: getAssociatedTokenAddressSync(mintA, maker.publicKey),
+      escrow,
+      vault: getAssociatedTokenAddressSync(mintA, escrow, true),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgramA: TOKEN_PROGRAM_ID,
+      tokenProgramB: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    const account = await program.account.escrow.fetch(escrow);
+    assert.equal(account.feeBps, feeBps);
+    assert.include(await eventNames(tx), "escrowMade");
+  });
+
+  it("Fee changes don't affect live offers", async () => {
+    await program.methods.updateConfig(1_000, treasury.publicKey)
+    .accountsPartial({
+      admin: maker.publicKey,
+      config,
+    })
+    .rpc();
+
+    const account = await program.account.escrow.fetch(escrow);
+    assert.equal(account.feeBps, feeBps);
+  });
+
+  it("Take", async () => {
+    const treasuryAtaB = getAssociatedTokenAddressSync(mintB, treasury.publicKey);
+    const makerAtaB = getAssociatedTokenAddressSync(mintB, maker.publicKey);
+
+    const tx = await program.methods.take(seed)
+    .accountsPartial({
+      maker: maker.publicKey,
+      taker: taker.publicKey,
+      mintA,
+      mintB,
+      config,
+      treasury: treasury.publicKey,
+      makerAtaB,
+      treasuryAtaB,
+      takerAtaA: getAssociatedTokenAddressSync(mintA, taker.publicKey),
+      takerAtaB: getAssociatedTokenAddressSync(mintB, taker.publicKey),
+      escrow,
+      vault: getAssociatedTokenAddressSync(mintA, escrow, true),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgramA: TOKEN_PROGRAM_ID,
+      tokenProgramB: TOKEN_PROGRAM_ID,
+    })
+    .signers([taker])
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    const fee = amount.toNumber() * feeBps / 10_000;
+    assert.equal(Number((await getAccount(provider.connection, treasuryAtaB)).amount), fee);
+    assert.equal(Number((await getAccount(provider.connection, makerAtaB)).amount), amount.toNumber() - fee);
+    assert.include(await eventNames(tx), "escrowTaken");
+  });
+
+  // Accounts shared by `make`, `refund` and `expire` for an escrow of mint A
+  const lifecycleAccounts = (seed: BN) => {
+    const escrow = escrowAddress(seed);
+    return {
+      maker: maker.publicKey,
+      mintA,
+      makerAtaA: getAssociatedTokenAddressSync(mintA, maker.publicKey),
+      escrow,
+      vault: getAssociatedTokenAddressSync(mintA, escrow, true),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgramA: TOKEN_PROGRAM_ID,
+    };
+  };
+
+  it("Update and refund", async () => {
+    const seed = new BN(3);
+    const accounts = lifecycleAccounts(seed);
+    await mintTo(provider.connection, maker, mintA, accounts.makerAtaA, maker, amount.toNumber());
+
+    await program.methods.make(seed, amount)
+    .accountsPartial({ ...accounts, mintB, config, tokenProgramB: TOKEN_PROGRAM_ID })
+    .rpc();
+
+    const expiresAt = new BN(Math.floor(Date.now() / 1000) + 3600);
+    const updateTx = await program.methods.updateEscrow(seed, expiresAt)
+    .accountsPartial({ maker: maker.publicKey, escrow: accounts.escrow })
+    .rpc();
+    assert.include(await eventNames(updateTx), "escrowUpdated");
+    const updated = await program.account.escrow.fetch(accounts.escrow);
+    assert.ok(updated.expiresAt.eq(expiresAt));
+    assert.ok(updated.recieveAmount.eq(amount));
+
+    try {
+      await program.methods.refund(seed)
+      .accountsPartial({ ...accounts, signer: taker.publicKey })
+      .signers([taker])
+      .rpc();
+      assert.fail("only the maker can refund");
+    } catch (err) {
+      assert.include(err.toString(), "NotMaker");
+    }
+
+    const tx = await program.methods.refund(seed)
+    .accountsPartial({ ...accounts, signer: maker.publicKey })
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    assert.include(await eventNames(tx), "escrowRefunded");
+    assert.equal(Number((await getAccount(provider.connection, accounts.makerAtaA)).amount), amount.toNumber());
+    assert.isNull(await provider.connection.getAccountInfo(accounts.escrow));
+  });
+
+  it("Expired escrows can't be taken and anyone can return them", async () => {
+    const seed = new BN(4);
+    const accounts = lifecycleAccounts(seed);
+
+    await program.methods.make(seed, amount)
+    .accountsPartial({ ...accounts, mintB, config, tokenProgramB: TOKEN_PROGRAM_ID })
+    .rpc();
+
+    try {
+      await program.methods.expire(seed)
+      .accountsPartial({ ...accounts, signer: taker.publicKey })
+      .signers([taker])
+      .rpc();
+      assert.fail("escrows without an expiry never expire");
+    } catch (err) {
+      assert.include(err.toString(), "NotExpired");
+    }
+
+    await program.methods.updateEscrow(seed, new BN(Math.floor(Date.now() / 1000) + 3))
+    .accountsPartial({ maker: maker.publicKey, escrow: accounts.escrow })
+    .rpc();
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+
+    try {
+      await program.methods.take(seed)
+      .accountsPartial({
+        maker: maker.publicKey,
+        taker: taker.publicKey,
+        mintA,
+        mintB,
+        config,
+        treasury: treasury.publicKey,
+        makerAtaB: getAssociatedTokenAddressSync(mintB, maker.publicKey),
+        treasuryAtaB: getAssociatedTokenAddressSync(mintB, treasury.publicKey),
+        takerAtaA: getAssociatedTokenAddressSync(mintA, taker.publicKey),
+        takerAtaB: getAssociatedTokenAddressSync(mintB, taker.publicKey),
+        escrow: accounts.escrow,
+        vault: accounts.vault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgramA: TOKEN_PROGRAM_ID,
+        tokenProgramB: TOKEN_PROGRAM_ID,
+      })
+      .signers([taker])
+      .rpc();
+      assert.fail("expired escrows can't be taken");
+    } catch (err) {
+      assert.include(err.toString(), "Expired");
+    }
+
+    const tx = await program.methods.expire(seed)
+    .accountsPartial({ ...accounts, signer: taker.publicKey })
+    .signers([taker])
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    assert.include(await eventNames(tx), "escrowExpired");
+    assert.equal(Number((await getAccount(provider.connection, accounts.makerAtaA)).amount), amount.toNumber());
+    assert.isNull(await provider.connection.getAccountInfo(accounts.escrow));
+  });
+});
+
+describe("anchor-escrow transfer hook", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AnchorEscrow as Program<AnchorEscrow>;
+  const hookProgram = anchor.workspace.TransferHook as Program<TransferHook>;
+
+  const maker = (provider.wallet as anchor.Wallet).payer;
+  const taker = anchor.web3.Keypair.generate();
+
+  const seed = new BN(2);
+  const amount = new BN(1_000_000);
+
+  const mintA = anchor.web3.Keypair.generate();
+  let mintB: anchor.web3.PublicKey;
+
+  const config = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId)[0];
+  const escrow = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("escrow"), maker.publicKey.toBuffer(), seed.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+  const extraAccountMetaList = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("extra-account-metas"), mintA.publicKey.toBuffer()],
+    hookProgram.programId
+  )[0];
+  const counter = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("counter")], hookProgram.programId)[0];
+
+  // Extra accounts the token program needs to invoke the hook on mint A
+  const hookAccounts = [
+    { pubkey: extraAccountMetaList, isSigner: false, isWritable: false },
+    { pubkey: counter, isSigner: false, isWritable: true },
+    { pubkey: hookProgram.programId, isSigner: false, isWritable: false },
+  ];
+
+  it("Setup hook mint", async () => {
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(taker.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL)
+    );
+
+    const mintLen = getMintLen([ExtensionType.TransferHook]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+    const tx = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: maker.publicKey,
+        newAccountPubkey: mintA.publicKey,
+        space: mintLen,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferHookInstruction(mintA.publicKey, maker.publicKey, hookProgram.programId, TOKEN_2022_PROGRAM_ID),
+      createInitializeMintInstruction(mintA.publicKey, 6, maker.publicKey, null, TOKEN_2022_PROGRAM_ID),
+    );
+    await provider.sendAndConfirm(tx, [mintA]);
+
+    await hookProgram.methods.initializeExtraAccountMetaList()
+    .accountsPartial({
+      payer: maker.publicKey,
+      extraAccountMetaList,
+      mint: mintA.publicKey,
+      counter,
+    })
+    .rpc();
+
+    mintB = await createMint(provider.connection, maker, maker.publicKey, null, 6, undefined, undefined, TOKEN_2022_PROGRAM_ID);
+
+    const makerAtaA = await getOrCreateAssociatedTokenAccount(provider.connection, maker, mintA.publicKey, maker.publicKey, false, undefined, undefined, TOKEN_2022_PROGRAM_ID);
+    const takerAtaB = await getOrCreateAssociatedTokenAccount(provider.connection, maker, mintB, taker.publicKey, false, undefined, undefined, TOKEN_2022_PROGRAM_ID);
+
+    await mintTo(provider.connection, maker, mintA.publicKey, makerAtaA.address, maker, amount.toNumber(), [], undefined, TOKEN_2022_PROGRAM_ID);
+    await mintTo(provider.connection, maker, mintB, takerAtaB.address, maker, amount.toNumber(), [], undefined, TOKEN_2022_PROGRAM_ID);
+  });
+
+  it("Make and take with a transfer hook mint", async () => {
+    await program.methods.make(seed, amount)
+    .accountsPartial({
+      maker: maker.publicKey,
+      mintA: mintA.publicKey,
+      mintB,
+      config,
+      makerAtaA: getAssociatedTokenAddressSync(mintA.publicKey, maker.publicKey, false, TOKEN_2022_PROGRAM_ID),
+      escrow,
+      vault: getAssociatedTokenAddressSync(mintA.publicKey, escrow, true, TOKEN_2022_PROGRAM_ID),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgramA: TOKEN_2022_PROGRAM_ID,
+      tokenProgramB: TOKEN_2022_PROGRAM_ID,
+    })
+    .remainingAccounts(hookAccounts)
+    .rpc();
+
+    const treasury = (await program.account.escrowConfig.fetch(config)).treasury;
+
+    await program.methods.take(seed)
+    .accountsPartial({
+      maker: maker.publicKey,
+      taker: taker.publicKey,
+      mintA: mintA.publicKey,
+      mintB,
+      config,
+      treasury,
+      makerAtaB: getAssociatedTokenAddressSync(mintB, maker.publicKey, false, TOKEN_2022_PROGRAM_ID),
+      treasuryAtaB: getAssociatedTokenAddressSync(mintB, treasury, false, TOKEN_2022_PROGRAM_ID),
+      takerAtaA: getAssociatedTokenAddressSync(mintA.publicKey, taker.publicKey, false, TOKEN_2022_PROGRAM_ID),
+      takerAtaB: getAssociatedTokenAddressSync(mintB, taker.publicKey, false, TOKEN_2022_PROGRAM_ID),
+      escrow,
+      vault: getAssociatedTokenAddressSync(mintA.publicKey, escrow, true, TOKEN_2022_PROGRAM_ID),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgramA: TOKEN_2022_PROGRAM_ID,
+      tokenProgramB: TOKEN_2022_PROGRAM_ID,
+    })
+    .remainingAccounts(hookAccounts)
+    .signers([taker])
+    .rpc();
+
+    // One hooked transfer into the vault on make, one out of it on take
+    assert.equal((await hookProgram.account.counter.fetch(counter)).transfers.toNumber(), 2);
+  });
+});
+
+describe("anchor-escrow nft", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AnchorEscrow as Program<AnchorEscrow>;
+
+  const maker = (provider.wallet as anchor.Wallet).payer;
+  const taker = anchor.web3.Keypair.generate();
+
+  const umi = createUmi(provider.connection);
+  const authority = createSignerFromKeypair(umi, umi.eddsa.createKeypairFromSecretKey(new Uint8Array(maker.secretKey)));
+  umi.use(keypairIdentity(authority));
+  umi.use(mplTokenMetadata());
+
+  const amount = new BN(1_000_000);
+
+  const config = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("config")], program.programId)[0];
+  const escrowAddress = (seed: BN) => anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("escrow"), maker.publicKey.toBuffer(), seed.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+
+  const toPublicKey = (signer: KeypairSigner) => new anchor.web3.PublicKey(signer.publicKey);
+
+  let collection: KeypairSigner;
+  let otherCollection: KeypairSigner;
+  let makerNft: KeypairSigner; // verified member owned by the maker
+  let takerNft: KeypairSigner; // verified member owned by the taker
+  let unverifiedNft: KeypairSigner; // claims the collection without being verified, owned by the taker
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let treasury: anchor.web3.PublicKey;
+
+  const mintNft = async (owner: anchor.web3.PublicKey, collectionMint: KeypairSigner, verify: boolean) => {
+    const mint = generateSigner(umi);
+    await createNft(umi, {
+      mint,
+      tokenOwner: publicKey(owner.toBase58()),
+      name: "GM",
+      uri: "https://arweave.net/123",
+      sellerFeeBasisPoints: percentAmount(0),
+      collection: { verified: false, key: collectionMint.publicKey },
+    }).sendAndConfirm(umi);
+
+    if (verify) {
+      await verifySizedCollectionItem(umi, {
+        metadata: findMetadataPda(umi, { mint: mint.publicKey }),
+        collectionAuthority: authority,
+        collectionMint: collectionMint.publicKey,
+        collection: findMetadataPda(umi, { mint: collectionMint.publicKey }),
+        collectionMasterEditionAccount: findMasterEditionPda(umi, { mint: collectionMint.publicKey }),
+      }).sendAndConfirm(umi);
+    }
+    return mint;
+  };
+
+  const nftAccounts = (nft: KeypairSigner) => ({
+    metadata: new anchor.web3.PublicKey(findMetadataPda(umi, { mint: nft.publicKey })[0]),
+    edition: new anchor.web3.PublicKey(findMasterEditionPda(umi, { mint: nft.publicKey })[0]),
+  });
+
+  it("Setup collections, NFTs and mints", async () => {
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(taker.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL)
+    );
+
+    collection = generateSigner(umi);
+    otherCollection = generateSigner(umi);
+    for (const mint of [collection, otherCollection]) {
+      await createNft(umi, {
+        mint,
+        name: "GM",
+        uri: "https://arweave.net/123",
+        sellerFeeBasisPoints: percentAmount(0),
+        collectionDetails: { __kind: "V1", size: 0 },
+      }).sendAndConfirm(umi);
+    }
+
+    makerNft = await mintNft(maker.publicKey, collection, true);
+    takerNft = await mintNft(taker.publicKey, collection, true);
+    unverifiedNft = await mintNft(taker.publicKey, collection, false);
+
+    mintA = await createMint(provider.connection, maker, maker.publicKey, null, 6);
+    mintB = await createMint(provider.connection, maker, maker.publicKey, null, 6);
+
+    const makerAtaA = await getOrCreateAssociatedTokenAccount(provider.connection, maker, mintA, maker.publicKey);
+    const takerAtaB = await getOrCreateAssociatedTokenAccount(provider.connection, maker, mintB, taker.publicKey);
+    await mintTo(provider.connection, maker, mintA, makerAtaA.address, maker, amount.toNumber() * 2);
+    await mintTo(provider.connection, maker, mintB, takerAtaB.address, maker, amount.toNumber());
+
+    treasury = (await program.account.escrowConfig.fetch(config)).treasury;
+  });
+
+  it("Reject NFT escrows for the wrong collection", async () => {
+    const seed = new BN(10);
+    const escrow = escrowAddress(seed);
+    const nftMint = toPublicKey(makerNft);
+
+    try {
+      await program.methods.makeNft(seed, amount)
+      .accountsPartial({
+        maker: maker.publicKey,
+        mintA: nftMint,
+        collection: toPublicKey(otherCollection),
+        mintB,
+        config,
+        ...nftAccounts(makerNft),
+        makerAtaA: getAssociatedTokenAddressSync(nftMint, maker.publicKey),
+        escrow,
+        vault: getAssociatedTokenAddressSync(nftMint, escrow, true),
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+      assert.fail("NFTs outside the collection should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "IncorrectCollection");
+    }
+  });
+
+  it("Make and take an NFT escrow", async () => {
+    const seed = new BN(10);
+    const escrow = escrowAddress(seed);
+    const nftMint = toPublicKey(makerNft);
+    const vault = getAssociatedTokenAddressSync(nftMint, escrow, true);
+
+    await program.methods.makeNft(seed, amount)
+    .accountsPartial({
+      maker: maker.publicKey,
+      mintA: nftMint,
+      collection: toPublicKey(collection),
+      mintB,
+      config,
+      ...nftAccounts(makerNft),
+      makerAtaA: getAssociatedTokenAddressSync(nftMint, maker.publicKey),
+      escrow,
+      vault,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+    assert.equal(Number((await getAccount(provider.connection, vault)).amount), 1);
+    const { feeBps, collection: escrowCollection } = await program.account.escrow.fetch(escrow);
+    assert.ok(escrowCollection.equals(toPublicKey(collection)));
+
+    const makerAtaB = getAssociatedTokenAddressSync(mintB, maker.publicKey);
+    const treasuryAtaB = getAssociatedTokenAddressSync(mintB, treasury);
+    const takerAtaA = getAssociatedTokenAddressSync(nftMint, taker.publicKey);
+
+    const tx = await program.methods.take(seed)
+    .accountsPartial({
+      maker: maker.publicKey,
+      taker: taker.publicKey,
+      mintA: nftMint,
+      mintB,
+      config,
+      treasury,
+      makerAtaB,
+      treasuryAtaB,
+      takerAtaA,
+      takerAtaB: getAssociatedTokenAddressSync(mintB, taker.publicKey),
+      escrow,
+      vault,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgramA: TOKEN_PROGRAM_ID,
+      tokenProgramB: TOKEN_PROGRAM_ID,
+    })
+    .signers([taker])
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    const fee = amount.toNumber() * feeBps / 10_000;
+    assert.equal(Number((await getAccount(provider.connection, takerAtaA)).amount), 1);
+    assert.equal(Number((await getAccount(provider.connection, makerAtaB)).amount), amount.toNumber() - fee);
+    assert.equal(Number((await getAccount(provider.connection, treasuryAtaB)).amount), fee);
+  });
+
+  // Maker deposits `amount` of mint A for any verified NFT of `collection`
+  const makeCollectionOffer = async (seed: BN) => {
+    const escrow = escrowAddress(seed);
+    await program.methods.makeCollectionOffer(seed, amount)
+    .accountsPartial({
+      maker: maker.publicKey,
+      mintA,
+      mintB: toPublicKey(collection),
+      config,
+      makerAtaA: getAssociatedTokenAddressSync(mintA, maker.publicKey),
+      escrow,
+      vault: getAssociatedTokenAddressSync(mintA, escrow, true),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgramA: TOKEN_PROGRAM_ID,
+      tokenProgramB: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+    return escrow;
+  };
+
+  const takeNftAccounts = (escrow: anchor.web3.PublicKey, nft: KeypairSigner) => {
+    const nftMint = toPublicKey(nft);
+    return {
+      maker: maker.publicKey,
+      taker: taker.publicKey,
+      mintA,
+      nftMint,
+      collection: toPublicKey(collection),
+      ...nftAccounts(nft),
+      config,
+      treasury,
+      makerNftAta: getAssociatedTokenAddressSync(nftMint, maker.publicKey),
+      takerNftAta: getAssociatedTokenAddressSync(nftMint, taker.publicKey),
+      takerAtaA: getAssociatedTokenAddressSync(mintA, taker.publicKey),
+      treasuryAtaA: getAssociatedTokenAddressSync(mintA, treasury),
+      escrow,
+      vault: getAssociatedTokenAddressSync(mintA, escrow, true),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  };
+
+  it("Reject unverified members on collection offers", async () => {
+    const seed = new BN(11);
+    const escrow = await makeCollectionOffer(seed);
+
+    try {
+      await program.methods.takeNft(seed)
+      .accountsPartial(takeNftAccounts(escrow, unverifiedNft))
+      .signers([taker])
+      .rpc();
+      assert.fail("unverified collection members should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "CollectionNotVerified");
+    }
+  });
+
+  it("Fill a collection offer with a verified member", async () => {
+    const seed = new BN(11);
+    const escrow = escrowAddress(seed);
+    const accounts = takeNftAccounts(escrow, takerNft);
+    const { feeBps } = await program.account.escrow.fetch(escrow);
+
+    const tx = await program.methods.takeNft(seed)
+    .accountsPartial(accounts)
+    .signers([taker])
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    // The fee comes out of the mint A vault
+    const fee = amount.toNumber() * feeBps / 10_000;
+    assert.equal(Number((await getAccount(provider.connection, accounts.makerNftAta)).amount), 1);
+    assert.equal(Number((await getAccount(provider.connection, accounts.takerAtaA)).amount), amount.toNumber() - fee);
+    assert.equal(Number((await getAccount(provider.connection, accounts.treasuryAtaA)).amount), fee);
+    assert.isNull(await provider.connection.getAccountInfo(escrow));
+  });
 });
@@ -2,3 +2,6 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+#[constant]
+pub const BPS_DENOMINATOR: u16 = 10_000;
@@ -4,4 +4,44 @@ use anchor_lang::prelude::*;
 pub enum ErrorCode {
     #[msg("Custom error message")]
     CustomError,
+    #[msg("Marketplace name must be 1 to 32 bytes")]
+    InvalidName,
+    #[msg("Fee cannot exceed 10000 basis points")]
+    InvalidFee,
+    #[msg("Price must be greater than zero")]
+    InvalidPrice,
+    #[msg("Incorrect collection")]
+    IncorrectCollection,
+    #[msg("Collection not verified")]
+    CollectionNotVerified,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Creator accounts don't match the metadata's verified creators")]
+    InvalidCreators,
+    #[msg("Payment mint is not allowed on this marketplace")]
+    PaymentMintNotAllowed,
+    #[msg("Payment mint is already allowed")]
+    PaymentMintAlreadyAllowed,
+    #[msg("Too many payment mints")]
+    TooManyPaymentMints,
+    #[msg("Payment accounts don't match the listing's payment mint")]
+    PaymentMintMismatch,
+    #[msg("NFT doesn't match the bid")]
+    BidMismatch,
+    #[msg("Invalid auction parameters")]
+    InvalidAuction,
+    #[msg("Wrong auction kind for this instruction")]
+    WrongAuctionKind,
+    #[msg("Auction hasn't started yet")]
+    AuctionNotStarted,
+    #[msg("Auction has ended")]
+    AuctionEnded,
+    #[msg("Auction hasn't ended yet")]
+    AuctionNotEnded,
+    #[msg("Auction already has bids")]
+    AuctionHasBids,
+    #[msg("Bid is below the reserve or minimum increment")]
+    BidTooLow,
+    #[msg("Account doesn't match the auction's highest bidder")]
+    InvalidBidder,
 }
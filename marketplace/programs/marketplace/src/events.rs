@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct Sale {
+    pub marketplace: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub mint: Pubkey,
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub fee: u64,
+    pub royalties: u64,
+}
+
+#[event]
+pub struct AuctionBid {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub end_time: i64,
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{MasterEditionAccount, Metadata, MetadataAccount},
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{error::ErrorCode, utils::PaySale, Bid, Marketplace, Sale};
+
+#[derive(Accounts)]
+pub struct AcceptBid<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>,
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+            b"edition",
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: Account<'info, MasterEditionAccount>,
+    #[account(
+        mut,
+        close = bidder,
+        has_one = bidder,
+        seeds = [
+            b"bid",
+            marketplace.key().as_ref(),
+            bidder.key().as_ref(),
+            bid.target.as_ref(),
+        ],
+        bump = bid.bump,
+        constraint = bid.matches(&mint.key(), &metadata) @ ErrorCode::BidMismatch,
+    )]
+    pub bid: Account<'info, Bid>,
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> AcceptBid<'info> {
+    // Splits the escrowed price the same way as a purchase: the marketplace fee to the treasury,
+    // royalties to the verified creators passed as writable remaining accounts in metadata order,
+    // and the rest to the seller. Returns the total royalties paid.
+    pub fn send_payment(&mut self, creators: &[AccountInfo<'info>]) -> Result<u64> {
+        let price = self.bid.price;
+        self.pay_sale(
+            &self.marketplace,
+            &self.metadata,
+            price,
+            creators,
+            &self.treasury.to_account_info(),
+            &self.seller.to_account_info(),
+        )
+    }
+
+    pub fn send_nft(&mut self, royalties: u64) -> Result<()> {
+        let accounts = TransferChecked {
+            from: self.seller_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.bidder_ata.to_account_info(),
+            authority: self.seller.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+
+        transfer_checked(ctx, 1, self.mint.decimals)?;
+
+        emit!(Sale {
+            marketplace: self.marketplace.key(),
+            maker: self.seller.key(),
+            taker: self.bidder.key(),
+            mint: self.mint.key(),
+            price: self.bid.price,
+            payment_mint: None,
+            fee: self.marketplace.fee_for(self.bid.price)?,
+            royalties,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> PaySale<'info> for AcceptBid<'info> {
+    // The bid account is owned by this program, so its escrow is moved directly.
+    fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.bid.sub_lamports(amount)?;
+        to.add_lamports(amount)?;
+
+        Ok(())
+    }
+}
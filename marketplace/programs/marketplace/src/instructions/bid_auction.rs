@@ -0,0 +1,70 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{error::ErrorCode, Auction, AuctionBid, Marketplace};
+
+#[derive(Accounts)]
+pub struct BidAuction<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    // Current highest bidder, refunded when outbid. Omit for the first bid or when the bidder
+    // is raising their own bid.
+    #[account(mut)]
+    pub previous_bidder: Option<SystemAccount<'info>>,
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    #[account(
+        mut,
+        seeds = [b"auction", marketplace.key().as_ref(), auction.mint.as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BidAuction<'info> {
+    pub fn bid(&mut self, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let outbid = self.auction.bid(self.bidder.key(), amount, now)?;
+
+        let accounts = Transfer {
+            from: self.bidder.to_account_info(),
+            to: self.auction.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+
+        transfer(ctx, amount)?;
+
+        if let Some((previous, refund)) = outbid {
+            let previous_bidder = if previous == self.bidder.key() {
+                self.bidder.to_account_info()
+            } else {
+                let previous_bidder = self
+                    .previous_bidder
+                    .as_ref()
+                    .ok_or(ErrorCode::InvalidBidder)?;
+                require_keys_eq!(previous_bidder.key(), previous, ErrorCode::InvalidBidder);
+                previous_bidder.to_account_info()
+            };
+
+            // The auction account is owned by this program, so the escrow is moved directly.
+            self.auction.sub_lamports(refund)?;
+            previous_bidder.add_lamports(refund)?;
+        }
+
+        emit!(AuctionBid {
+            auction: self.auction.key(),
+            bidder: self.bidder.key(),
+            amount,
+            end_time: self.auction.end_time,
+        });
+
+        Ok(())
+    }
+}
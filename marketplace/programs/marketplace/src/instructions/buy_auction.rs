@@ -0,0 +1,163 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{utils::PaySale, Auction, Marketplace, Sale};
+
+// Buys the NFT out of a Dutch auction at its current price.
+#[derive(Accounts)]
+pub struct BuyAuction<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = maker_mint,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = maker_mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [b"auction", marketplace.key().as_ref(), maker_mint.key().as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, Auction>,
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> BuyAuction<'info> {
+    pub fn current_price(&self) -> Result<u64> {
+        self.auction.price_at(Clock::get()?.unix_timestamp)
+    }
+
+    // Pays the marketplace fee to the treasury, royalties to the verified creators passed as
+    // writable remaining accounts in metadata order, and the rest of `price` to the maker.
+    // Returns the total royalties paid.
+    pub fn send_payment(&mut self, price: u64, creators: &[AccountInfo<'info>]) -> Result<u64> {
+        self.pay_sale(
+            &self.marketplace,
+            &self.metadata,
+            price,
+            creators,
+            &self.treasury.to_account_info(),
+            &self.maker.to_account_info(),
+        )
+    }
+
+    pub fn send_nft(&mut self, price: u64, royalties: u64) -> Result<()> {
+        let marketplace_key = self.marketplace.key();
+        let mint_key = self.maker_mint.key();
+        let seeds = [
+            b"auction",
+            marketplace_key.as_ref(),
+            mint_key.as_ref(),
+            &[self.auction.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[&seeds[..]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.maker_mint.to_account_info(),
+            to: self.taker_ata.to_account_info(),
+            authority: self.auction.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(ctx, 1, self.maker_mint.decimals)?;
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.auction.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+
+        close_account(ctx)?;
+
+        emit!(Sale {
+            marketplace: self.marketplace.key(),
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            mint: self.maker_mint.key(),
+            price,
+            payment_mint: None,
+            fee: self.marketplace.fee_for(price)?,
+            royalties,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> PaySale<'info> for BuyAuction<'info> {
+    fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let accounts = Transfer {
+            from: self.taker.to_account_info(),
+            to: to.clone(),
+        };
+
+        let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+
+        transfer(ctx, amount)
+    }
+}
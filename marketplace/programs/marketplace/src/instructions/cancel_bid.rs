@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{Bid, Marketplace};
+
+// Closing the bid refunds the escrowed price along with its rent.
+#[derive(Accounts)]
+pub struct CancelBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    #[account(
+        mut,
+        close = bidder,
+        has_one = bidder,
+        seeds = [
+            b"bid",
+            marketplace.key().as_ref(),
+            bidder.key().as_ref(),
+            bid.target.as_ref(),
+        ],
+        bump = bid.bump,
+    )]
+    pub bid: Account<'info, Bid>,
+}
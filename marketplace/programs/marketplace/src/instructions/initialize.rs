@@ -1,9 +1,77 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::{error::ErrorCode, Marketplace, BPS_DENOMINATOR, MAX_NAME_LEN};
 
 #[derive(Accounts)]
-pub struct Initialize {}
+#[instruction(name: String)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"marketplace", name.as_bytes()],
+        bump,
+        space = Marketplace::INIT_SPACE,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    // Funded to rent-exempt here, so fees smaller than that can be paid into it
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"rewards", marketplace.key().as_ref()],
+        bump,
+        mint::decimals = 6,
+        mint::authority = marketplace,
+    )]
+    pub rewards_mint: InterfaceAccount<'info, Mint>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Initialize<'info> {
+    pub fn init(&mut self, name: String, fee: u16, bumps: &InitializeBumps) -> Result<()> {
+        require!(!name.is_empty() && name.len() <= MAX_NAME_LEN, ErrorCode::InvalidName);
+        require!(fee <= BPS_DENOMINATOR, ErrorCode::InvalidFee);
+
+        self.marketplace.set_inner(Marketplace {
+            admin: self.admin.key(),
+            fee,
+            bump: bumps.marketplace,
+            treasury_bump: bumps.treasury,
+            rewards_bump: bumps.rewards_mint,
+            payment_mints: Vec::new(),
+            name,
+        });
+
+        self.fund_treasury()
+    }
+
+    fn fund_treasury(&self) -> Result<()> {
+        let rent = Rent::get()?
+            .minimum_balance(0)
+            .saturating_sub(self.treasury.lamports());
+        if rent == 0 {
+            return Ok(());
+        }
+
+        let accounts = Transfer {
+            from: self.admin.to_account_info(),
+            to: self.treasury.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
 
-pub fn handler(ctx: Context<Initialize>) -> Result<()> {
-    msg!("Greetings from: {{:?}}", ctx.program_id);
-    Ok(())
+        transfer(ctx, rent)
+    }
 }
@@ -1,3 +1,27 @@
 pub mod initialize;
+pub mod list;
+pub mod delist;
+pub mod purchase;
+pub mod payment_mints;
+pub mod place_bid;
+pub mod cancel_bid;
+pub mod accept_bid;
+pub mod create_auction;
+pub mod bid_auction;
+pub mod settle_auction;
+pub mod buy_auction;
+pub mod cancel_auction;
 
 pub use initialize::*;
+pub use list::*;
+pub use delist::*;
+pub use purchase::*;
+pub use payment_mints::*;
+pub use place_bid::*;
+pub use cancel_bid::*;
+pub use accept_bid::*;
+pub use create_auction::*;
+pub use bid_auction::*;
+pub use settle_auction::*;
+pub use buy_auction::*;
+pub use cancel_auction::*;
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{error::ErrorCode, Marketplace, MAX_PAYMENT_MINTS};
+
+#[derive(Accounts)]
+pub struct UpdatePaymentMints<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+}
+
+impl<'info> UpdatePaymentMints<'info> {
+    pub fn add_payment_mint(&mut self) -> Result<()> {
+        let mint = self.payment_mint.key();
+
+        require!(
+            !self.marketplace.accepts(&mint),
+            ErrorCode::PaymentMintAlreadyAllowed
+        );
+        require!(
+            self.marketplace.payment_mints.len() < MAX_PAYMENT_MINTS,
+            ErrorCode::TooManyPaymentMints
+        );
+
+        self.marketplace.payment_mints.push(mint);
+
+        Ok(())
+    }
+
+    // Existing listings in this mint can still be purchased; only new listings are blocked.
+    pub fn remove_payment_mint(&mut self) -> Result<()> {
+        let mint = self.payment_mint.key();
+
+        require!(
+            self.marketplace.accepts(&mint),
+            ErrorCode::PaymentMintNotAllowed
+        );
+
+        self.marketplace
+            .payment_mints
+            .retain(|allowed| *allowed != mint);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::{error::ErrorCode, Bid, BidKind, Marketplace};
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    // Collection mint for collection bids, the NFT mint otherwise
+    pub target: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = bidder,
+        seeds = [
+            b"bid",
+            marketplace.key().as_ref(),
+            bidder.key().as_ref(),
+            target.key().as_ref(),
+        ],
+        bump,
+        space = Bid::INIT_SPACE,
+    )]
+    pub bid: Account<'info, Bid>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlaceBid<'info> {
+    pub fn create_bid(&mut self, kind: BidKind, price: u64, bumps: &PlaceBidBumps) -> Result<()> {
+        require!(price > 0, ErrorCode::InvalidPrice);
+
+        self.bid.set_inner(Bid {
+            bidder: self.bidder.key(),
+            target: self.target.key(),
+            kind,
+            price,
+            bump: bumps.bid,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_funds(&mut self) -> Result<()> {
+        let accounts = Transfer {
+            from: self.bidder.to_account_info(),
+            to: self.bid.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+
+        transfer(ctx, self.bid.price)
+    }
+}
@@ -0,0 +1,265 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{error::ErrorCode, utils::PaySale, Listing, Marketplace, Sale};
+
+#[derive(Accounts)]
+pub struct Purchase<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = maker_mint,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = maker_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [marketplace.key().as_ref(), maker_mint.key().as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Account<'info, Listing>,
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    // Payment accounts, required only when the listing is priced in an SPL token
+    pub payment_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = taker,
+        token::token_program = payment_token_program,
+    )]
+    pub taker_payment_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = payment_mint,
+        associated_token::authority = maker,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub maker_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = payment_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub treasury_payment_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Purchase<'info> {
+    // Pays the marketplace fee to the treasury and the royalty to each verified creator, then
+    // the rest of the price to the maker, all in the listing's currency. Creators are passed as
+    // writable remaining accounts in metadata order: their wallets for SOL listings, or their
+    // token accounts for the payment mint otherwise. Returns the total royalties paid.
+    pub fn send_payment(&mut self, creators: &[AccountInfo<'info>]) -> Result<u64> {
+        self.check_payment_accounts()?;
+
+        let treasury = self.payee(self.treasury.to_account_info(), &self.treasury_payment_ata);
+        let maker = self.payee(self.maker.to_account_info(), &self.maker_payment_ata);
+
+        self.pay_sale(
+            &self.marketplace,
+            &self.metadata,
+            self.listing.price,
+            creators,
+            &treasury,
+            &maker,
+        )
+    }
+
+    fn check_payment_accounts(&self) -> Result<()> {
+        let Some(mint) = self.listing.payment_mint else {
+            return Ok(());
+        };
+
+        let payment_mint = self
+            .payment_mint
+            .as_ref()
+            .ok_or(ErrorCode::PaymentMintMismatch)?;
+        require_keys_eq!(payment_mint.key(), mint, ErrorCode::PaymentMintMismatch);
+        require!(
+            self.taker_payment_account.is_some()
+                && self.maker_payment_ata.is_some()
+                && self.treasury_payment_ata.is_some()
+                && self.payment_token_program.is_some(),
+            ErrorCode::PaymentMintMismatch
+        );
+
+        Ok(())
+    }
+
+    // Where `owner` gets paid: their wallet for SOL listings, their payment token account otherwise.
+    fn payee(
+        &self,
+        owner: AccountInfo<'info>,
+        payment_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+    ) -> AccountInfo<'info> {
+        match (self.listing.payment_mint, payment_account) {
+            (Some(_), Some(payment_account)) => payment_account.to_account_info(),
+            _ => owner,
+        }
+    }
+
+    pub fn send_nft(&mut self, royalties: u64) -> Result<()> {
+        let marketplace_key = self.marketplace.key();
+        let mint_key = self.maker_mint.key();
+        let seeds = [
+            marketplace_key.as_ref(),
+            mint_key.as_ref(),
+            &[self.listing.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[&seeds[..]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.maker_mint.to_account_info(),
+            to: self.taker_ata.to_account_info(),
+            authority: self.listing.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(ctx, 1, self.maker_mint.decimals)?;
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.listing.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+
+        close_account(ctx)?;
+
+        emit!(Sale {
+            marketplace: self.marketplace.key(),
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            mint: self.maker_mint.key(),
+            price: self.listing.price,
+            payment_mint: self.listing.payment_mint,
+            fee: self.marketplace.fee_for(self.listing.price)?,
+            royalties,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> PaySale<'info> for Purchase<'info> {
+    fn pays_lamports(&self) -> bool {
+        self.listing.payment_mint.is_none()
+    }
+
+    fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        if let (Some(_), Some(mint), Some(from), Some(token_program)) = (
+            self.listing.payment_mint,
+            &self.payment_mint,
+            &self.taker_payment_account,
+            &self.payment_token_program,
+        ) {
+            let accounts = TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.clone(),
+                authority: self.taker.to_account_info(),
+            };
+
+            let ctx = CpiContext::new(token_program.to_account_info(), accounts);
+
+            return transfer_checked(ctx, amount, mint.decimals);
+        }
+
+        let accounts = Transfer {
+            from: self.taker.to_account_info(),
+            to: to.clone(),
+        };
+
+        let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+
+        transfer(ctx, amount)
+    }
+
+    // SPL listings pay creators into token accounts, so those are checked by owner and mint.
+    fn check_creator(&self, creator: Pubkey, account: &AccountInfo<'info>) -> Result<()> {
+        match (self.listing.payment_mint, &self.payment_token_program) {
+            (Some(mint), Some(token_program)) => {
+                require_keys_eq!(
+                    *account.owner,
+                    token_program.key(),
+                    ErrorCode::InvalidCreators
+                );
+
+                let token_account =
+                    TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+                require_keys_eq!(token_account.owner, creator, ErrorCode::InvalidCreators);
+                require_keys_eq!(token_account.mint, mint, ErrorCode::InvalidCreators);
+            }
+            _ => require_keys_eq!(account.key(), creator, ErrorCode::InvalidCreators),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{Metadata, MetadataAccount},
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{error::ErrorCode, utils::PaySale, Auction, AuctionKind, Marketplace, Sale};
+
+// Anyone can settle an English auction once it has ended. The NFT goes to the highest bidder,
+// or back to the maker if nobody met the reserve.
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub winner: SystemAccount<'info>,
+    #[account(
+        seeds = [b"marketplace", marketplace.name.as_bytes()],
+        bump = marketplace.bump,
+    )]
+    pub marketplace: Account<'info, Marketplace>,
+    pub maker_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = maker_mint,
+        associated_token::authority = winner,
+        associated_token::token_program = token_program,
+    )]
+    pub winner_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = maker_mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [b"auction", marketplace.key().as_ref(), maker_mint.key().as_ref()],
+        bump = auction.bump,
+        constraint = auction.highest_bidder.unwrap_or(auction.maker) == winner.key() @ ErrorCode::InvalidBidder,
+    )]
+    pub auction: Account<'info, Auction>,
+    #[account(
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            maker_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", marketplace.key().as_ref()],
+        bump = marketplace.treasury_bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SettleAuction<'info> {
+    // Splits the winning bid like a purchase: the marketplace fee to the treasury, royalties
+    // to the verified creators passed as writable remaining accounts in metadata order, and
+    // the rest to the maker. Returns the total royalties paid.
+    pub fn send_payment(&mut self, creators: &[AccountInfo<'info>]) -> Result<u64> {
+        require!(
+            matches!(self.auction.kind, AuctionKind::English { .. }),
+            ErrorCode::WrongAuctionKind
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= self.auction.end_time,
+            ErrorCode::AuctionNotEnded
+        );
+
+        if self.auction.highest_bidder.is_none() {
+            return Ok(0);
+        }
+
+        let price = self.auction.highest_bid;
+        self.pay_sale(
+            &self.marketplace,
+            &self.metadata,
+            price,
+            creators,
+            &self.treasury.to_account_info(),
+            &self.maker.to_account_info(),
+        )
+    }
+
+    pub fn send_nft(&mut self, royalties: u64) -> Result<()> {
+        let marketplace_key = self.marketplace.key();
+        let mint_key = self.maker_mint.key();
+        let seeds = [
+            b"auction",
+            marketplace_key.as_ref(),
+            mint_key.as_ref(),
+            &[self.auction.bump],
+        ];
+        let signer_seeds: &[&[&[u8]]] = &[&seeds[..]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.maker_mint.to_account_info(),
+            to: self.winner_ata.to_account_info(),
+            authority: self.auction.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+
+        transfer_checked(ctx, 1, self.maker_mint.decimals)?;
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.auction.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+
+        close_account(ctx)?;
+
+        if self.auction.highest_bidder.is_some() {
+            emit!(Sale {
+                marketplace: self.marketplace.key(),
+                maker: self.maker.key(),
+                taker: self.winner.key(),
+                mint: self.maker_mint.key(),
+                price: self.auction.highest_bid,
+                payment_mint: None,
+                fee: self.marketplace.fee_for(self.auction.highest_bid)?,
+                royalties,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<'info> PaySale<'info> for SettleAuction<'info> {
+    // The auction account is owned by this program, so its escrow is moved directly.
+    fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.auction.sub_lamports(amount)?;
+        to.add_lamports(amount)?;
+
+        Ok(())
+    }
+}
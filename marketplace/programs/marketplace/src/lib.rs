@@ -1,11 +1,14 @@
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 
 pub use constants::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -15,7 +18,75 @@ declare_id!("CQyKqxgrrjnzYJwWdjkhQrQRhCCu6V1XJpZbuPifJnwR");
 pub mod marketplace {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        initialize::handler(ctx)
+    pub fn initialize(ctx: Context<Initialize>, name: String, fee: u16) -> Result<()> {
+        ctx.accounts.init(name, fee, &ctx.bumps)
+    }
+
+    pub fn list(ctx: Context<List>, price: u64) -> Result<()> {
+        ctx.accounts.create_listing(price, &ctx.bumps)?;
+        ctx.accounts.deposit_nft()
+    }
+
+    pub fn delist(ctx: Context<Delist>) -> Result<()> {
+        ctx.accounts.withdraw_nft()
+    }
+
+    pub fn purchase<'info>(ctx: Context<'_, '_, '_, 'info, Purchase<'info>>) -> Result<()> {
+        let royalties = ctx.accounts.send_payment(ctx.remaining_accounts)?;
+        ctx.accounts.send_nft(royalties)
+    }
+
+    pub fn add_payment_mint(ctx: Context<UpdatePaymentMints>) -> Result<()> {
+        ctx.accounts.add_payment_mint()
+    }
+
+    pub fn remove_payment_mint(ctx: Context<UpdatePaymentMints>) -> Result<()> {
+        ctx.accounts.remove_payment_mint()
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, kind: BidKind, price: u64) -> Result<()> {
+        ctx.accounts.create_bid(kind, price, &ctx.bumps)?;
+        ctx.accounts.deposit_funds()
+    }
+
+    pub fn cancel_bid(_ctx: Context<CancelBid>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn accept_bid<'info>(ctx: Context<'_, '_, '_, 'info, AcceptBid<'info>>) -> Result<()> {
+        let royalties = ctx.accounts.send_payment(ctx.remaining_accounts)?;
+        ctx.accounts.send_nft(royalties)
+    }
+
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        kind: AuctionKind,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_auction(kind, start_time, end_time, &ctx.bumps)?;
+        ctx.accounts.deposit_nft()
+    }
+
+    pub fn bid_auction(ctx: Context<BidAuction>, amount: u64) -> Result<()> {
+        ctx.accounts.bid(amount)
+    }
+
+    pub fn settle_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleAuction<'info>>,
+    ) -> Result<()> {
+        let royalties = ctx.accounts.send_payment(ctx.remaining_accounts)?;
+        ctx.accounts.send_nft(royalties)
+    }
+
+    pub fn buy_auction<'info>(ctx: Context<'_, '_, '_, 'info, BuyAuction<'info>>) -> Result<()> {
+        let price = ctx.accounts.current_price()?;
+        let royalties = ctx.accounts.send_payment(price, ctx.remaining_accounts)?;
+        ctx.accounts.send_nft(price, royalties)
+    }
+
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        ctx.accounts.withdraw_nft()
     }
 }
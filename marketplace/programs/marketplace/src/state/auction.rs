@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionKind {
+    // Ascending SOL bids. The first bid must meet the reserve and each later one must beat the
+    // highest by `min_increment`. A bid landing within `extension` seconds of the end pushes the
+    // end back to `extension` seconds from that bid.
+    English {
+        reserve_price: u64,
+        min_increment: u64,
+        extension: i64,
+    },
+    // The price falls linearly from `start_price` at the start to `end_price` at the end, then
+    // stays there until someone buys.
+    Dutch {
+        start_price: u64,
+        end_price: u64,
+    },
+}
+
+// The NFT sits in a vault owned by the auction PDA. For English auctions the highest bid is
+// escrowed as lamports on the auction account itself, on top of its rent.
+#[account]
+pub struct Auction {
+    pub maker: Pubkey,
+    pub mint: Pubkey,
+    pub kind: AuctionKind,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub highest_bid: u64,
+    pub highest_bidder: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl Space for Auction {
+    const INIT_SPACE: usize = 8 + 32 + 32 + (1 + 8 + 8 + 8) + 8 + 8 + 8 + (1 + 32) + 1;
+}
+
+impl Auction {
+    pub fn validate(&self, now: i64) -> Result<()> {
+        require!(
+            self.start_time < self.end_time && now < self.end_time,
+            ErrorCode::InvalidAuction
+        );
+
+        match self.kind {
+            AuctionKind::English {
+                reserve_price,
+                min_increment,
+                extension,
+            } => require!(
+                reserve_price > 0 && min_increment > 0 && extension >= 0,
+                ErrorCode::InvalidAuction
+            ),
+            AuctionKind::Dutch {
+                start_price,
+                end_price,
+            } => require!(
+                end_price > 0 && start_price > end_price,
+                ErrorCode::InvalidAuction
+            ),
+        }
+
+        Ok(())
+    }
+
+    // Records a bid of `amount` at `now`, extending the auction if it lands in the final
+    // `extension` seconds. Returns the outbid bidder and the amount owed back to them.
+    pub fn bid(&mut self, bidder: Pubkey, amount: u64, now: i64) -> Result<Option<(Pubkey, u64)>> {
+        let AuctionKind::English {
+            reserve_price,
+            min_increment,
+            extension,
+        } = self.kind
+        else {
+            return err!(ErrorCode::WrongAuctionKind);
+        };
+
+        require!(now >= self.start_time, ErrorCode::AuctionNotStarted);
+        require!(now < self.end_time, ErrorCode::AuctionEnded);
+
+        let min_bid = match self.highest_bidder {
+            Some(_) => self
+                .highest_bid
+                .checked_add(min_increment)
+                .ok_or(ErrorCode::Overflow)?,
+            None => reserve_price,
+        };
+        require!(amount >= min_bid, ErrorCode::BidTooLow);
+
+        let outbid = self
+            .highest_bidder
+            .map(|previous| (previous, self.highest_bid));
+
+        self.highest_bid = amount;
+        self.highest_bidder = Some(bidder);
+
+        if self.end_time - now < extension {
+            self.end_time = now + extension;
+        }
+
+        Ok(outbid)
+    }
+
+    // Dutch auction price at `now`.
+    pub fn price_at(&self, now: i64) -> Result<u64> {
+        let AuctionKind::Dutch {
+            start_price,
+            end_price,
+        } = self.kind
+        else {
+            return err!(ErrorCode::WrongAuctionKind);
+        };
+
+        require!(now >= self.start_time, ErrorCode::AuctionNotStarted);
+
+        if now >= self.end_time {
+            return Ok(end_price);
+        }
+
+        let elapsed = (now - self.start_time) as u128;
+        let duration = (self.end_time - self.start_time) as u128;
+        let drop = (start_price - end_price) as u128 * elapsed / duration;
+
+        Ok(start_price - drop as u64)
+    }
+}
\ No newline at end of file
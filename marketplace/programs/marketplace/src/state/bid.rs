@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BidKind {
+    Collection, // any NFT verified as part of the `target` collection
+    Mint,       // only the `target` mint
+}
+
+// A standing offer in SOL. The price is escrowed as lamports on the bid account itself, on top
+// of its rent, and both go back to the bidder when the bid is cancelled or accepted.
+#[account]
+pub struct Bid {
+    pub bidder: Pubkey,
+    pub target: Pubkey,
+    pub kind: BidKind,
+    pub price: u64,
+    pub bump: u8,
+}
+
+impl Space for Bid {
+    const INIT_SPACE: usize = 8 + 32 + 32 + 1 + 8 + 1;
+}
+
+impl Bid {
+    pub fn matches(&self, mint: &Pubkey, metadata: &MetadataAccount) -> bool {
+        match self.kind {
+            BidKind::Mint => self.target == *mint,
+            BidKind::Collection => metadata
+                .collection
+                .as_ref()
+                .is_some_and(|collection| collection.verified && collection.key == self.target),
+        }
+    }
+}
\ No newline at end of file
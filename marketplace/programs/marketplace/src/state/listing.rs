@@ -7,11 +7,12 @@ pub struct Listing {
     pub maker: Pubkey,
     pub mint: Pubkey,
     pub price: u64,
+    pub payment_mint: Option<Pubkey>, // None for SOL
     pub bump: u8
 }
 
 //space implementation by ourselves
 impl Space for Listing{
 
-    const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 1 ;
+    const INIT_SPACE: usize = 8 + 32 + 32 + 8 + (1 + 32) + 1 ;
 }
\ No newline at end of file
@@ -1,4 +1,44 @@
 use anchor_lang::prelude::*;
 
+use crate::{error::ErrorCode, BPS_DENOMINATOR};
+
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_PAYMENT_MINTS: usize = 8;
+
+// One branded marketplace. Its PDA is seeded by name, so several marketplaces can run on
+// this program, each with its own fee, treasury, rewards mint and allowlist of SPL mints
+// listings may be priced in (SOL is always accepted).
 #[account]
-pub struct 
\ No newline at end of file
+pub struct Marketplace {
+    pub admin: Pubkey,
+    pub fee: u16, // basis points
+    pub bump: u8,
+    pub treasury_bump: u8,
+    pub rewards_bump: u8,
+    pub payment_mints: Vec<Pubkey>,
+    pub name: String,
+}
+
+impl Space for Marketplace {
+    const INIT_SPACE: usize =
+        8 + 32 + 2 + 1 + 1 + 1 + (4 + 32 * MAX_PAYMENT_MINTS) + (4 + MAX_NAME_LEN);
+}
+
+impl Marketplace {
+    // Marketplace cut of a sale at `price`.
+    pub fn fee_for(&self, price: u64) -> Result<u64> {
+        apply_bps(price, self.fee)
+    }
+
+    pub fn accepts(&self, payment_mint: &Pubkey) -> bool {
+        self.payment_mints.contains(payment_mint)
+    }
+}
+
+pub fn apply_bps(amount: u64, bps: u16) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / BPS_DENOMINATOR as u128;
+    Ok(share as u64)
+}
\ No newline at end of file
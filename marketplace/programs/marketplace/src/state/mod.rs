@@ -1,5 +1,9 @@
+pub mod auction;
+pub mod bid;
 pub mod listing;
 pub mod marketplace;
 
+pub use auction::*;
+pub use bid::*;
 pub use listing::*;
 pub use marketplace::*;
\ No newline at end of file
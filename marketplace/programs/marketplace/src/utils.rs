@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+
+use crate::{apply_bps, error::ErrorCode, Marketplace};
+
+// Royalty owed to each verified creator of an NFT sold at `price`, in metadata order.
+// Unverified creators are skipped and their share stays with the seller.
+pub fn creator_royalties(metadata: &MetadataAccount, price: u64) -> Result<Vec<(Pubkey, u64)>> {
+    let royalty = apply_bps(price, metadata.seller_fee_basis_points)?;
+
+    Ok(metadata
+        .creators
+        .iter()
+        .flatten()
+        .filter(|creator| creator.verified)
+        .map(|creator| {
+            let share = (royalty as u128 * creator.share as u128 / 100) as u64;
+            (creator.address, share)
+        })
+        .collect())
+}
+
+// Settles the price of a sale the same way for every instruction that sells an NFT. Each one
+// only supplies how funds move out of its escrow or the buyer's wallet.
+pub trait PaySale<'info> {
+    fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()>;
+
+    // Whether `pay` moves lamports rather than tokens.
+    fn pays_lamports(&self) -> bool {
+        true
+    }
+
+    // Checks that a remaining account is where `creator`'s royalty goes.
+    fn check_creator(&self, creator: Pubkey, account: &AccountInfo<'info>) -> Result<()> {
+        require_keys_eq!(account.key(), creator, ErrorCode::InvalidCreators);
+
+        Ok(())
+    }
+
+    // Pays the royalty of each verified creator to the accounts passed in metadata order, the
+    // marketplace fee to `treasury` and the rest of `price` to `seller`. Returns the total
+    // royalties paid.
+    //
+    // A lamport royalty too small to make an unfunded creator account rent exempt would fail
+    // the whole sale, so it's skipped and that share stays with the seller.
+    fn pay_sale(
+        &self,
+        marketplace: &Marketplace,
+        metadata: &MetadataAccount,
+        price: u64,
+        creators: &[AccountInfo<'info>],
+        treasury: &AccountInfo<'info>,
+        seller: &AccountInfo<'info>,
+    ) -> Result<u64> {
+        let fee = marketplace.fee_for(price)?;
+        let shares = creator_royalties(metadata, price)?;
+
+        require!(creators.len() == shares.len(), ErrorCode::InvalidCreators);
+
+        let rent = Rent::get()?;
+        let mut royalties = 0;
+        for ((creator, share), account) in shares.into_iter().zip(creators) {
+            self.check_creator(creator, account)?;
+
+            if self.pays_lamports()
+                && !rent.is_exempt(account.lamports().saturating_add(share), account.data_len())
+            {
+                continue;
+            }
+
+            self.pay(account, share)?;
+            royalties += share;
+        }
+
+        self.pay(treasury, fee)?;
+
+        let proceeds = price
+            .checked_sub(fee)
+            .and_then(|rest| rest.checked_sub(royalties))
+            .ok_or(ErrorCode::Overflow)?;
+        self.pay(seller, proceeds)?;
+
+        Ok(royalties)
+    }
+}
@@ -1,16 +1,590 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Marketplace } from "../target/types/marketplace";
+import { createNft, findMasterEditionPda, findMetadataPda, mplTokenMetadata, verifyCreatorV1, verifySizedCollectionItem } from "@metaplex-foundation/mpl-token-metadata";
+import { createUmi } from "@metaplex-foundation/umi-bundle-defaults";
+import { KeypairSigner, PublicKey, createSignerFromKeypair, generateSigner, keypairIdentity, none, percentAmount } from "@metaplex-foundation/umi";
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID, createMint, getAccount, getAssociatedTokenAddressSync, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
+import { assert } from "chai";
 
 describe("marketplace", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.Marketplace as Program<Marketplace>;
 
+  const umi = createUmi(provider.connection);
+  const payer = provider.wallet as NodeWallet;
+  const creator = createSignerFromKeypair(umi, umi.eddsa.createKeypairFromSecretKey(new Uint8Array(payer.payer.secretKey)));
+  umi.use(keypairIdentity(creator));
+  umi.use(mplTokenMetadata());
+
+  const name = "gm market";
+  const fee = 250;
+  const price = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL);
+
+  const marketplace = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("marketplace"), Buffer.from(name)], program.programId)[0];
+  const treasury = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("treasury"), marketplace.toBuffer()], program.programId)[0];
+  const rewardsMint = anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("rewards"), marketplace.toBuffer()], program.programId)[0];
+
+  const taker = anchor.web3.Keypair.generate();
+  // The NFT's only verified creator, kept apart from every buyer and seller so royalties can be told apart
+  const artist = generateSigner(umi);
+
+  let collectionMint: KeypairSigner;
+  let nftMint: KeypairSigner;
+
+  const toPublicKey = (key: PublicKey) => new anchor.web3.PublicKey(key);
+
+  const listingAccounts = () => {
+    const makerMint = toPublicKey(nftMint.publicKey);
+    const listing = anchor.web3.PublicKey.findProgramAddressSync([marketplace.toBuffer(), makerMint.toBuffer()], program.programId)[0];
+    return {
+      maker: provider.wallet.publicKey,
+      marketplace,
+      makerMint,
+      makerAta: getAssociatedTokenAddressSync(makerMint, provider.wallet.publicKey),
+      vault: getAssociatedTokenAddressSync(makerMint, listing, true),
+      listing,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  };
+
   it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
+    const tx = await program.methods.initialize(name, fee)
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      marketplace,
+      treasury,
+      rewardsMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    const account = await program.account.marketplace.fetch(marketplace);
+    assert.equal(account.name, name);
+    assert.equal(account.fee, fee);
+
+    // funded so that fees below rent-exempt can be paid into it
+    assert.equal(await provider.connection.getBalance(treasury), await provider.connection.getMinimumBalanceForRentExemption(0));
+  });
+
+  it("Mint and verify collection NFT", async () => {
+    collectionMint = generateSigner(umi);
+    await createNft(umi, {
+      mint: collectionMint,
+      name: "GM",
+      symbol: "GM",
+      uri: "https://arweave.net/123",
+      sellerFeeBasisPoints: percentAmount(5.5),
+      collectionDetails: { __kind: "V1", size: 10 },
+    }).sendAndConfirm(umi);
+
+    nftMint = generateSigner(umi);
+    await createNft(umi, {
+      mint: nftMint,
+      name: "GM",
+      symbol: "GM",
+      uri: "https://arweave.net/123",
+      sellerFeeBasisPoints: percentAmount(5.5),
+      creators: [{ address: artist.publicKey, verified: false, share: 100 }],
+      collection: { verified: false, key: collectionMint.publicKey },
+    }).sendAndConfirm(umi);
+
+    await verifyCreatorV1(umi, {
+      metadata: findMetadataPda(umi, { mint: nftMint.publicKey }),
+      authority: artist,
+    }).sendAndConfirm(umi);
+
+    await verifySizedCollectionItem(umi, {
+      metadata: findMetadataPda(umi, { mint: nftMint.publicKey }),
+      collectionAuthority: creator,
+      collectionMint: collectionMint.publicKey,
+      collection: findMetadataPda(umi, { mint: collectionMint.publicKey }),
+      collectionMasterEditionAccount: findMasterEditionPda(umi, { mint: collectionMint.publicKey }),
+    }).sendAndConfirm(umi);
+  });
+
+  it("List", async () => {
+    const accounts = listingAccounts();
+
+    const tx = await program.methods.list(price)
+    .accountsPartial({
+      ...accounts,
+      collectionMint: toPublicKey(collectionMint.publicKey),
+      metadata: toPublicKey(findMetadataPda(umi, { mint: nftMint.publicKey })[0]),
+      masterEdition: toPublicKey(findMasterEditionPda(umi, { mint: nftMint.publicKey })[0]),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    assert.equal(Number((await getAccount(provider.connection, accounts.vault)).amount), 1);
+    assert.ok((await program.account.listing.fetch(accounts.listing)).price.eq(price));
+  });
+
+  it("Delist", async () => {
+    const accounts = listingAccounts();
+
+    const tx = await program.methods.delist()
+    .accountsPartial(accounts)
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    assert.equal(Number((await getAccount(provider.connection, accounts.makerAta)).amount), 1);
+    assert.isNull(await provider.connection.getAccountInfo(accounts.listing));
+  });
+
+  it("Purchase", async () => {
+    const accounts = listingAccounts();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(taker.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL)
+    );
+
+    await program.methods.list(price)
+    .accountsPartial({
+      ...accounts,
+      collectionMint: toPublicKey(collectionMint.publicKey),
+      metadata: toPublicKey(findMetadataPda(umi, { mint: nftMint.publicKey })[0]),
+      masterEdition: toPublicKey(findMasterEditionPda(umi, { mint: nftMint.publicKey })[0]),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+    const treasuryBefore = await provider.connection.getBalance(treasury);
+    const artistBefore = await provider.connection.getBalance(toPublicKey(artist.publicKey));
+    const takerAta = getAssociatedTokenAddressSync(accounts.makerMint, taker.publicKey);
+
+    const purchase = (creators: anchor.web3.PublicKey[]) => program.methods.purchase()
+    .accountsPartial({
+      taker: taker.publicKey,
+      maker: accounts.maker,
+      marketplace,
+      makerMint: accounts.makerMint,
+      takerAta,
+      vault: accounts.vault,
+      listing: accounts.listing,
+      metadata: toPublicKey(findMetadataPda(umi, { mint: nftMint.publicKey })[0]),
+      treasury,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .remainingAccounts(creators.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+    .signers([taker]);
+
+    try {
+      await purchase([anchor.web3.Keypair.generate().publicKey]).rpc();
+      assert.fail("purchase should reject creators that don't match the metadata");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidCreators");
+    }
+
+    const tx = await purchase([toPublicKey(artist.publicKey)]).rpc();
     console.log("Your transaction signature", tx);
+
+    assert.equal(Number((await getAccount(provider.connection, takerAta)).amount), 1);
+    assert.equal(await provider.connection.getBalance(treasury) - treasuryBefore, price.toNumber() * fee / 10_000);
+    assert.equal(await provider.connection.getBalance(toPublicKey(artist.publicKey)) - artistBefore, price.toNumber() * 550 / 10_000);
+    assert.isNull(await provider.connection.getAccountInfo(accounts.listing));
+  });
+
+  it("Purchase with an SPL payment mint", async () => {
+    // The taker relists the NFT priced in a 6-decimal token and the provider wallet buys it back
+    const tokenPrice = 100_000_000;
+    const paymentMint = await createMint(provider.connection, payer.payer, provider.wallet.publicKey, null, 6);
+
+    await program.methods.addPaymentMint()
+    .accountsPartial({
+      admin: provider.wallet.publicKey,
+      marketplace,
+      paymentMint,
+    })
+    .rpc();
+
+    const makerMint = toPublicKey(nftMint.publicKey);
+    const listing = anchor.web3.PublicKey.findProgramAddressSync([marketplace.toBuffer(), makerMint.toBuffer()], program.programId)[0];
+    const vault = getAssociatedTokenAddressSync(makerMint, listing, true);
+    const metadata = toPublicKey(findMetadataPda(umi, { mint: nftMint.publicKey })[0]);
+
+    await program.methods.list(new anchor.BN(tokenPrice))
+    .accountsPartial({
+      maker: taker.publicKey,
+      marketplace,
+      makerMint,
+      collectionMint: toPublicKey(collectionMint.publicKey),
+      paymentMint,
+      makerAta: getAssociatedTokenAddressSync(makerMint, taker.publicKey),
+      vault,
+      listing,
+      metadata,
+      masterEdition: toPublicKey(findMasterEditionPda(umi, { mint: nftMint.publicKey })[0]),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([taker])
+    .rpc();
+
+    assert.ok((await program.account.listing.fetch(listing)).paymentMint.equals(paymentMint));
+
+    const buyerPaymentAccount = await getOrCreateAssociatedTokenAccount(provider.connection, payer.payer, paymentMint, provider.wallet.publicKey);
+    await mintTo(provider.connection, payer.payer, paymentMint, buyerPaymentAccount.address, payer.payer, tokenPrice);
+
+    const makerPaymentAta = getAssociatedTokenAddressSync(paymentMint, taker.publicKey);
+    const treasuryPaymentAta = getAssociatedTokenAddressSync(paymentMint, treasury, true);
+    const artistPaymentAccount = await getOrCreateAssociatedTokenAccount(provider.connection, payer.payer, paymentMint, toPublicKey(artist.publicKey));
+
+    const tx = await program.methods.purchase()
+    .accountsPartial({
+      taker: provider.wallet.publicKey,
+      maker: taker.publicKey,
+      marketplace,
+      makerMint,
+      takerAta: getAssociatedTokenAddressSync(makerMint, provider.wallet.publicKey),
+      vault,
+      listing,
+      metadata,
+      treasury,
+      paymentMint,
+      takerPaymentAccount: buyerPaymentAccount.address,
+      makerPaymentAta,
+      treasuryPaymentAta,
+      paymentTokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .remainingAccounts([{ pubkey: artistPaymentAccount.address, isSigner: false, isWritable: true }])
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    const marketFee = tokenPrice * fee / 10_000;
+    const royalty = tokenPrice * 550 / 10_000;
+    assert.equal(Number((await getAccount(provider.connection, treasuryPaymentAta)).amount), marketFee);
+    assert.equal(Number((await getAccount(provider.connection, makerPaymentAta)).amount), tokenPrice - marketFee - royalty);
+    assert.equal(Number((await getAccount(provider.connection, artistPaymentAccount.address)).amount), royalty);
+    assert.equal(Number((await getAccount(provider.connection, buyerPaymentAccount.address)).amount), 0);
+  });
+
+  const bidAddress = (bidder: anchor.web3.PublicKey, target: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("bid"), marketplace.toBuffer(), bidder.toBuffer(), target.toBuffer()], program.programId)[0];
+
+  it("Place and cancel a bid", async () => {
+    const target = toPublicKey(nftMint.publicKey);
+    const bid = bidAddress(taker.publicKey, target);
+
+    await program.methods.placeBid({ mint: {} }, price)
+    .accountsPartial({
+      bidder: taker.publicKey,
+      marketplace,
+      target,
+      bid,
+    })
+    .signers([taker])
+    .rpc();
+
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(8 + 32 + 32 + 1 + 8 + 1);
+    assert.equal(await provider.connection.getBalance(bid), rent + price.toNumber());
+
+    const tx = await program.methods.cancelBid()
+    .accountsPartial({
+      bidder: taker.publicKey,
+      marketplace,
+      bid,
+    })
+    .signers([taker])
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    assert.isNull(await provider.connection.getAccountInfo(bid));
+  });
+
+  it("Accept a collection bid", async () => {
+    const target = toPublicKey(collectionMint.publicKey);
+    const bid = bidAddress(taker.publicKey, target);
+    const mint = toPublicKey(nftMint.publicKey);
+
+    await program.methods.placeBid({ collection: {} }, price)
+    .accountsPartial({
+      bidder: taker.publicKey,
+      marketplace,
+      target,
+      bid,
+    })
+    .signers([taker])
+    .rpc();
+
+    const treasuryBefore = await provider.connection.getBalance(treasury);
+    const bidderAta = getAssociatedTokenAddressSync(mint, taker.publicKey);
+
+    const tx = await program.methods.acceptBid()
+    .accountsPartial({
+      seller: provider.wallet.publicKey,
+      bidder: taker.publicKey,
+      marketplace,
+      mint,
+      sellerAta: getAssociatedTokenAddressSync(mint, provider.wallet.publicKey),
+      bidderAta,
+      metadata: toPublicKey(findMetadataPda(umi, { mint: nftMint.publicKey })[0]),
+      masterEdition: toPublicKey(findMasterEditionPda(umi, { mint: nftMint.publicKey })[0]),
+      bid,
+      treasury,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .remainingAccounts([{ pubkey: toPublicKey(artist.publicKey), isSigner: false, isWritable: true }])
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    assert.equal(Number((await getAccount(provider.connection, bidderAta)).amount), 1);
+    assert.equal(await provider.connection.getBalance(treasury) - treasuryBefore, price.toNumber() * fee / 10_000);
+    assert.isNull(await provider.connection.getAccountInfo(bid));
+  });
+
+  // An NFT outside the collection, with no creators to pay, held by the provider wallet
+  let looseMint: KeypairSigner;
+
+  const acceptBid = (bid: anchor.web3.PublicKey, mint: anchor.web3.PublicKey) => program.methods.acceptBid()
+  .accountsPartial({
+    seller: provider.wallet.publicKey,
+    bidder: taker.publicKey,
+    marketplace,
+    mint,
+    sellerAta: getAssociatedTokenAddressSync(mint, provider.wallet.publicKey),
+    bidderAta: getAssociatedTokenAddressSync(mint, taker.publicKey),
+    metadata: toPublicKey(findMetadataPda(umi, { mint: mint.toBase58() as PublicKey })[0]),
+    masterEdition: toPublicKey(findMasterEditionPda(umi, { mint: mint.toBase58() as PublicKey })[0]),
+    bid,
+    treasury,
+    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  });
+
+  it("Accepting a collection bid with an NFT outside the collection fails", async () => {
+    looseMint = generateSigner(umi);
+    await createNft(umi, {
+      mint: looseMint,
+      name: "GM",
+      symbol: "GM",
+      uri: "https://arweave.net/123",
+      sellerFeeBasisPoints: percentAmount(0),
+      creators: none(),
+    }).sendAndConfirm(umi);
+
+    const target = toPublicKey(collectionMint.publicKey);
+    const bid = bidAddress(taker.publicKey, target);
+
+    await program.methods.placeBid({ collection: {} }, price)
+    .accountsPartial({
+      bidder: taker.publicKey,
+      marketplace,
+      target,
+      bid,
+    })
+    .signers([taker])
+    .rpc();
+
+    try {
+      await acceptBid(bid, toPublicKey(looseMint.publicKey)).rpc();
+      assert.fail("a collection bid should only accept NFTs verified in that collection");
+    } catch (err) {
+      assert.include(err.toString(), "BidMismatch");
+    }
+
+    await program.methods.cancelBid()
+    .accountsPartial({
+      bidder: taker.publicKey,
+      marketplace,
+      bid,
+    })
+    .signers([taker])
+    .rpc();
+  });
+
+  it("Accept a mint bid", async () => {
+    const mint = toPublicKey(looseMint.publicKey);
+    const bid = bidAddress(taker.publicKey, mint);
+
+    await program.methods.placeBid({ mint: {} }, price)
+    .accountsPartial({
+      bidder: taker.publicKey,
+      marketplace,
+      target: mint,
+      bid,
+    })
+    .signers([taker])
+    .rpc();
+
+    const treasuryBefore = await provider.connection.getBalance(treasury);
+
+    const tx = await acceptBid(bid, mint).rpc();
+    console.log("Your transaction signature", tx);
+
+    assert.equal(Number((await getAccount(provider.connection, getAssociatedTokenAddressSync(mint, taker.publicKey))).amount), 1);
+    assert.equal(await provider.connection.getBalance(treasury) - treasuryBefore, price.toNumber() * fee / 10_000);
+    assert.isNull(await provider.connection.getAccountInfo(bid));
+  });
+
+  const auctionAddress = (mint: anchor.web3.PublicKey) =>
+    anchor.web3.PublicKey.findProgramAddressSync([Buffer.from("auction"), marketplace.toBuffer(), mint.toBuffer()], program.programId)[0];
+
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+  const outbidder = anchor.web3.Keypair.generate();
+
+  it("English auction refunds outbid bidders and extends late bids", async () => {
+    // The taker won the collection bid and now auctions the NFT
+    const makerMint = toPublicKey(nftMint.publicKey);
+    const auction = auctionAddress(makerMint);
+    const vault = getAssociatedTokenAddressSync(makerMint, auction, true);
+    const reservePrice = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10);
+    const minIncrement = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 20);
+    const extension = 10;
+
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(outbidder.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL)
+    );
+
+    const now = Math.floor(Date.now() / 1000);
+    const endTime = now + 4;
+    await program.methods.createAuction(
+      { english: { reservePrice, minIncrement, extension: new anchor.BN(extension) } },
+      new anchor.BN(now - 10),
+      new anchor.BN(endTime),
+    )
+    .accountsPartial({
+      maker: taker.publicKey,
+      marketplace,
+      makerMint,
+      collectionMint: toPublicKey(collectionMint.publicKey),
+      makerAta: getAssociatedTokenAddressSync(makerMint, taker.publicKey),
+      vault,
+      auction,
+      metadata: toPublicKey(findMetadataPda(umi, { mint: nftMint.publicKey })[0]),
+      masterEdition: toPublicKey(findMasterEditionPda(umi, { mint: nftMint.publicKey })[0]),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([taker])
+    .rpc();
+
+    try {
+      await program.methods.bidAuction(reservePrice.subn(1))
+      .accountsPartial({ bidder: provider.wallet.publicKey, marketplace, auction })
+      .rpc();
+      assert.fail("bids below the reserve should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "BidTooLow");
+    }
+
+    await program.methods.bidAuction(reservePrice)
+    .accountsPartial({ bidder: provider.wallet.publicKey, marketplace, auction })
+    .rpc();
+
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(8 + 32 + 32 + (1 + 8 + 8 + 8) + 8 + 8 + 8 + (1 + 32) + 1);
+    const winningBid = reservePrice.add(minIncrement);
+
+    await program.methods.bidAuction(winningBid)
+    .accountsPartial({
+      bidder: outbidder.publicKey,
+      previousBidder: provider.wallet.publicKey,
+      marketplace,
+      auction,
+    })
+    .signers([outbidder])
+    .rpc();
+
+    // Only the highest bid stays escrowed, and landing inside the window pushed the end back
+    const account = await program.account.auction.fetch(auction);
+    assert.equal(await provider.connection.getBalance(auction), rent + winningBid.toNumber());
+    assert.ok(account.highestBidder.equals(outbidder.publicKey));
+    assert.isAbove(account.endTime.toNumber(), endTime);
+
+    await sleep((account.endTime.toNumber() - Math.floor(Date.now() / 1000) + 2) * 1000);
+
+    const treasuryBefore = await provider.connection.getBalance(treasury);
+    const winnerAta = getAssociatedTokenAddressSync(makerMint, outbidder.publicKey);
+
+    const tx = await program.methods.settleAuction()
+    .accountsPartial({
+      payer: provider.wallet.publicKey,
+      maker: taker.publicKey,
+      winner: outbidder.publicKey,
+      marketplace,
+      makerMint,
+      winnerAta,
+      vault,
+      auction,
+      metadata: toPublicKey(findMetadataPda(umi, { mint: nftMint.publicKey })[0]),
+      treasury,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .remainingAccounts([{ pubkey: toPublicKey(artist.publicKey), isSigner: false, isWritable: true }])
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    assert.equal(Number((await getAccount(provider.connection, winnerAta)).amount), 1);
+    assert.equal(await provider.connection.getBalance(treasury) - treasuryBefore, winningBid.toNumber() * fee / 10_000);
+    assert.isNull(await provider.connection.getAccountInfo(auction));
+  });
+
+  it("Dutch auction sells at the decayed price", async () => {
+    const makerMint = toPublicKey(nftMint.publicKey);
+    const auction = auctionAddress(makerMint);
+    const vault = getAssociatedTokenAddressSync(makerMint, auction, true);
+    const startPrice = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL);
+    const endPrice = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 2);
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods.createAuction(
+      { dutch: { startPrice, endPrice } },
+      new anchor.BN(now - 30),
+      new anchor.BN(now + 30),
+    )
+    .accountsPartial({
+      maker: outbidder.publicKey,
+      marketplace,
+      makerMint,
+      collectionMint: toPublicKey(collectionMint.publicKey),
+      makerAta: getAssociatedTokenAddressSync(makerMint, outbidder.publicKey),
+      vault,
+      auction,
+      metadata: toPublicKey(findMetadataPda(umi, { mint: nftMint.publicKey })[0]),
+      masterEdition: toPublicKey(findMasterEditionPda(umi, { mint: nftMint.publicKey })[0]),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([outbidder])
+    .rpc();
+
+    const treasuryBefore = await provider.connection.getBalance(treasury);
+    const takerAta = getAssociatedTokenAddressSync(makerMint, taker.publicKey);
+
+    const tx = await program.methods.buyAuction()
+    .accountsPartial({
+      taker: taker.publicKey,
+      maker: outbidder.publicKey,
+      marketplace,
+      makerMint,
+      takerAta,
+      vault,
+      auction,
+      metadata: toPublicKey(findMetadataPda(umi, { mint: nftMint.publicKey })[0]),
+      treasury,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .remainingAccounts([{ pubkey: toPublicKey(artist.publicKey), isSigner: false, isWritable: true }])
+    .signers([taker])
+    .rpc();
+    console.log("Your transaction signature", tx);
+
+    // Roughly halfway through, so the price paid sits strictly between the start and the floor
+    const pricePaid = (await provider.connection.getBalance(treasury) - treasuryBefore) * 10_000 / fee;
+    assert.equal(Number((await getAccount(provider.connection, takerAta)).amount), 1);
+    assert.isBelow(pricePaid, startPrice.toNumber());
+    assert.isAbove(pricePaid, endPrice.toNumber());
   });
 });